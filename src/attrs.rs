@@ -0,0 +1,140 @@
+use serde::de::{MapAccess, Visitor};
+use serde::ser::SerializeMap;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
+// region:    --- Types
+
+/// The attributes of an `Elem`, as an ordered list of `(name, value)` pairs.
+///
+/// Serialized as a JSON object, with the properties in the list order
+/// (the source order by default, see `SelectOptions::with_attrs_order`).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Attrs(Vec<(String, String)>);
+
+/// The order of the `Elem::attrs`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AttrsOrder {
+	/// The order of the attributes in the HTML source.
+	#[default]
+	Source,
+	/// Sorted by attribute name.
+	Sorted,
+}
+
+// endregion: --- Types
+
+// region:    --- Public API
+
+impl Attrs {
+	/// Returns the value of the attribute (exact name match).
+	pub fn get(&self, name: &str) -> Option<&String> {
+		self.0.iter().find(|(n, _)| n == name).map(|(_, v)| v)
+	}
+
+	pub fn contains_key(&self, name: &str) -> bool {
+		self.get(name).is_some()
+	}
+
+	/// Iterates over the `(name, value)` pairs, in order.
+	pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+		self.0.iter().map(|(n, v)| (n.as_str(), v.as_str()))
+	}
+
+	pub fn len(&self) -> usize {
+		self.0.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.0.is_empty()
+	}
+
+	/// Sorts the attributes by name.
+	pub fn sort(&mut self) {
+		self.0.sort_by(|a, b| a.0.cmp(&b.0));
+	}
+}
+
+impl<K: Into<String>, V: Into<String>> FromIterator<(K, V)> for Attrs {
+	fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+		Self(iter.into_iter().map(|(k, v)| (k.into(), v.into())).collect())
+	}
+}
+
+impl IntoIterator for Attrs {
+	type Item = (String, String);
+	type IntoIter = std::vec::IntoIter<(String, String)>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.0.into_iter()
+	}
+}
+
+// endregion: --- Public API
+
+// region:    --- Serde
+
+impl Serialize for Attrs {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		let mut map = serializer.serialize_map(Some(self.0.len()))?;
+		for (name, value) in &self.0 {
+			map.serialize_entry(name, value)?;
+		}
+		map.end()
+	}
+}
+
+impl<'de> Deserialize<'de> for Attrs {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		deserializer.deserialize_map(AttrsVisitor)
+	}
+}
+
+/// Keeps the JSON object properties order.
+struct AttrsVisitor;
+
+impl<'de> Visitor<'de> for AttrsVisitor {
+	type Value = Attrs;
+
+	fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+		formatter.write_str("a map of attribute names to string values")
+	}
+
+	fn visit_map<A: MapAccess<'de>>(self, mut access: A) -> Result<Self::Value, A::Error> {
+		let mut attrs = Vec::with_capacity(access.size_hint().unwrap_or(0));
+		while let Some(entry) = access.next_entry::<String, String>()? {
+			attrs.push(entry);
+		}
+		Ok(Attrs(attrs))
+	}
+}
+
+// endregion: --- Serde
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	type TestResult<T> = core::result::Result<T, Box<dyn std::error::Error>>;
+
+	use super::*;
+
+	#[test]
+	fn test_attrs_serde_keeps_order() -> TestResult<()> {
+		// -- Setup & Fixtures
+		let json = r#"{"src":"a.png","alt":"A","class":"logo"}"#;
+
+		// -- Exec
+		let attrs: Attrs = serde_json::from_str(json)?;
+
+		// -- Check
+		let names: Vec<&str> = attrs.iter().map(|(n, _)| n).collect();
+		assert_eq!(names, ["src", "alt", "class"]);
+		assert_eq!(attrs.get("alt").map(|s| s.as_str()), Some("A"));
+		assert_eq!(serde_json::to_string(&attrs)?, json);
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
@@ -0,0 +1,636 @@
+use super::ChunkOptions;
+use crate::source::SourceMap;
+use crate::support::{NON_CONTENT_TAGS, collapse_whitespace, is_text_block_tag};
+use crate::{Document, Result, SourceSpan, text_from_node, text_from_nodes};
+use ego_tree::NodeRef;
+use html_escape::encode_text;
+use scraper::{ElementRef, node::Node};
+use serde::{Deserialize, Serialize};
+
+// region:    --- Types
+
+/// A chunk of a page, under a heading hierarchy (see `chunk`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Chunk {
+	/// The titles of the headings the chunk is under, from the top (e.g., `["Guide", "Install"]`).
+	pub breadcrumb: Vec<String>,
+	/// The HTML of the chunk blocks (one per line).
+	pub html: String,
+	/// The text of the chunk blocks (separated by a blank line).
+	pub text: String,
+	/// The location in the HTML source, from the first to the last element of the chunk
+	/// (`None` when the chunk is text only). The pieces of a split block have the span of their items/rows,
+	/// or of the whole block when split by lines or words.
+	pub span: Option<SourceSpan>,
+}
+
+/// A unit of content, never split unless larger than `ChunkOptions::max_chars`.
+struct Block<'a> {
+	html: String,
+	text: String,
+	span: Option<SourceSpan>,
+	/// The element of the block (`None` for an inline run or a split piece).
+	element: Option<ElementRef<'a>>,
+}
+
+/// The flattened content of a page.
+enum Item<'a> {
+	Heading {
+		level: usize,
+		title: String,
+		block: Block<'a>,
+	},
+	Block(Block<'a>),
+	SectionStart,
+	SectionEnd,
+}
+
+// endregion: --- Types
+
+// region:    --- Constants
+
+/// Tags kept whole in a chunk (unless oversized).
+#[rustfmt::skip]
+const ATOMIC_TAGS: &[&str] = &[
+	"p", "pre", "table", "ul", "ol", "dl", "menu", "figure", "h1", "h2", "h3", "h4", "h5", "h6",
+];
+
+/// Tags starting (and ending) a chunk, and scoping the headings they contain.
+const SECTION_TAGS: &[&str] = &["section", "article"];
+
+/// Tags split by items when oversized.
+const LIST_TAGS: &[&str] = &["ul", "ol", "dl", "menu"];
+
+// endregion: --- Constants
+
+/// Splits a page into chunks following its heading hierarchy, for retrieval pipelines (e.g., on `slim` output).
+///
+/// - A chunk starts at each heading (`<h1>` to `<h6>`, up to `ChunkOptions::max_heading_level`), and at the start
+///   and end of each `<section>`/`<article>`. The headings of a section only apply inside it.
+/// - The content is split into blocks (paragraphs, tables, lists, `<pre>`, figures, and the inline content
+///   of containers), never broken inside, and a chunk gets as many blocks as fit `ChunkOptions::max_chars`.
+/// - Only a block larger than `max_chars` is split: lists by items, tables by rows, and other blocks by lines
+///   (`<pre>`) or words. An oversized item (or cell) is split by words, in its own item (or row and cell).
+/// - Non-content elements (`<head>`, `<script>`, etc.) are skipped.
+pub fn chunk(html_content: &str, options: impl Into<ChunkOptions>) -> Result<Vec<Chunk>> {
+	Ok(Document::parse(html_content).chunk(options))
+}
+
+/// Chunks an already parsed document (see `Document::chunk`).
+pub(crate) fn chunks_from_document(doc: &Document, options: &ChunkOptions) -> Vec<Chunk> {
+	let source_map = doc.source_map();
+	let mut items = Vec::new();
+	collect_items(*doc.html().root_element(), options, source_map, &mut items);
+
+	let mut chunker = Chunker {
+		options,
+		source_map,
+		chunks: Vec::new(),
+		headings: Vec::new(),
+		section_floors: Vec::new(),
+		blocks: Vec::new(),
+		text_len: 0,
+	};
+	for item in items {
+		chunker.push(item);
+	}
+	chunker.flush();
+
+	chunker.chunks
+}
+
+// region:    --- Chunker
+
+/// Groups the items into chunks.
+struct Chunker<'a, 'o> {
+	options: &'o ChunkOptions,
+	source_map: &'o SourceMap,
+	chunks: Vec<Chunk>,
+	/// The current headings (level, title).
+	headings: Vec<(usize, String)>,
+	/// The number of headings at the start of each open section (not popped by the section headings).
+	section_floors: Vec<usize>,
+	/// The blocks of the current chunk.
+	blocks: Vec<Block<'a>>,
+	text_len: usize,
+}
+
+impl<'a> Chunker<'a, '_> {
+	fn push(&mut self, item: Item<'a>) {
+		match item {
+			Item::Heading { level, title, block } => {
+				self.flush();
+				let floor = self.section_floors.last().copied().unwrap_or(0);
+				while self.headings.len() > floor && self.headings.last().is_some_and(|(l, _)| *l >= level) {
+					self.headings.pop();
+				}
+				self.headings.push((level, title));
+				self.push_block(block);
+			}
+			Item::Block(block) => self.push_block(block),
+			Item::SectionStart => {
+				self.flush();
+				self.section_floors.push(self.headings.len());
+			}
+			Item::SectionEnd => {
+				self.flush();
+				let floor = self.section_floors.pop().unwrap_or(0);
+				self.headings.truncate(floor);
+			}
+		}
+	}
+
+	fn push_block(&mut self, block: Block<'a>) {
+		let len = block.text.chars().count();
+		if len > self.options.max_chars {
+			self.flush();
+			for piece in split_block(block, self.options, self.source_map) {
+				self.blocks.push(piece);
+				self.flush();
+			}
+			return;
+		}
+
+		if !self.blocks.is_empty() && self.text_len + len > self.options.max_chars {
+			self.flush();
+		}
+		self.text_len += len;
+		self.blocks.push(block);
+	}
+
+	fn flush(&mut self) {
+		if self.blocks.is_empty() {
+			return;
+		}
+		let blocks = std::mem::take(&mut self.blocks);
+		self.text_len = 0;
+
+		self.chunks.push(Chunk {
+			breadcrumb: self.headings.iter().map(|(_, title)| title.clone()).collect(),
+			html: blocks.iter().map(|b| b.html.as_str()).collect::<Vec<_>>().join("\n"),
+			text: blocks.iter().map(|b| b.text.as_str()).collect::<Vec<_>>().join("\n\n"),
+			span: union_spans(blocks.iter().map(|b| b.span)),
+		});
+	}
+}
+
+// endregion: --- Chunker
+
+// region:    --- Items
+
+/// The work of the items walk.
+enum Step<'a> {
+	Node(NodeRef<'a, Node>),
+	/// The end of the children of a container (flushes the inline run).
+	EndChildren,
+	EndSection,
+}
+
+/// Flattens the children of the node into items (headings, blocks, and section boundaries).
+/// Walked with a stack (no recursion), so deep documents are safe.
+fn collect_items<'a>(
+	parent: NodeRef<'a, Node>,
+	options: &ChunkOptions,
+	source_map: &SourceMap,
+	items: &mut Vec<Item<'a>>,
+) {
+	// The inline content (text and inline elements) between blocks, flushed before entering a block
+	let mut run: Vec<NodeRef<'a, Node>> = Vec::new();
+	let mut stack = vec![Step::EndChildren];
+	push_children(parent, &mut stack);
+
+	while let Some(step) = stack.pop() {
+		let child = match step {
+			Step::Node(child) => child,
+			Step::EndChildren => {
+				push_run(&mut run, options, source_map, items);
+				continue;
+			}
+			Step::EndSection => {
+				items.push(Item::SectionEnd);
+				continue;
+			}
+		};
+
+		let Some(el) = ElementRef::wrap(child) else {
+			if let Node::Text(_) = child.value() {
+				run.push(child);
+			}
+			continue;
+		};
+		let tag = el.value().name();
+		if NON_CONTENT_TAGS.contains(&tag) {
+			continue;
+		}
+		if !is_block(tag) {
+			run.push(child);
+			continue;
+		}
+
+		push_run(&mut run, options, source_map, items);
+		if let Some(level) = heading_level(tag).filter(|level| *level <= options.max_heading_level as usize) {
+			let block = element_block(el, options, source_map);
+			let title = collapse_whitespace(&block.text).trim().to_string();
+			if title.is_empty() {
+				continue;
+			}
+			items.push(Item::Heading { level, title, block });
+		} else if SECTION_TAGS.contains(&tag) {
+			items.push(Item::SectionStart);
+			stack.push(Step::EndSection);
+			stack.push(Step::EndChildren);
+			push_children(child, &mut stack);
+		} else if !ATOMIC_TAGS.contains(&tag) && (matches!(tag, "html" | "body") || has_block_child(el)) {
+			stack.push(Step::EndChildren);
+			push_children(child, &mut stack);
+		} else {
+			let block = element_block(el, options, source_map);
+			if !block.text.is_empty() {
+				items.push(Item::Block(block));
+			}
+		}
+	}
+}
+
+/// Pushes the children in reverse order, so they are walked in order.
+fn push_children<'a>(node: NodeRef<'a, Node>, stack: &mut Vec<Step<'a>>) {
+	stack.extend(node.children().rev().map(Step::Node));
+}
+
+/// Adds the inline run as a block (when it has text), and clears it.
+fn push_run<'a>(
+	run: &mut Vec<NodeRef<'a, Node>>,
+	options: &ChunkOptions,
+	source_map: &SourceMap,
+	items: &mut Vec<Item<'a>>,
+) {
+	let nodes = std::mem::take(run);
+	let text = text_from_nodes(nodes.iter().copied(), &options.text_options);
+	if text.is_empty() {
+		return;
+	}
+	items.push(Item::Block(Block {
+		html: nodes
+			.iter()
+			.map(|node| node_html(*node))
+			.collect::<String>()
+			.trim()
+			.to_string(),
+		text,
+		span: union_spans(nodes.iter().map(|node| source_map.get(node.id()))),
+		element: None,
+	}));
+}
+
+fn element_block<'a>(el: ElementRef<'a>, options: &ChunkOptions, source_map: &SourceMap) -> Block<'a> {
+	Block {
+		html: el.html(),
+		text: text_from_node(*el, &options.text_options),
+		span: source_map.get(el.id()),
+		element: Some(el),
+	}
+}
+
+// endregion: --- Items
+
+// region:    --- Split
+
+/// Splits an oversized block: lists by items, tables by rows, and others by lines (`<pre>`) or words.
+fn split_block<'a>(block: Block<'a>, options: &ChunkOptions, source_map: &SourceMap) -> Vec<Block<'a>> {
+	let Some(el) = block.element else {
+		return split_text(&block.text, block.span, &["p"], options);
+	};
+	let tag = el.value().name();
+
+	let parts: Vec<ElementRef> = if LIST_TAGS.contains(&tag) {
+		el.children().filter_map(ElementRef::wrap).collect()
+	} else if tag == "table" {
+		table_rows(el)
+	} else {
+		Vec::new()
+	};
+
+	if !parts.is_empty() {
+		split_parts(&[tag], parts, options, source_map)
+	} else if LIST_TAGS.contains(&tag) || tag == "table" {
+		// e.g., a list with only text, which cannot be wrapped in its tag
+		split_text(&block.text, block.span, &["p"], options)
+	} else {
+		split_text(&block.text, block.span, &[tag], options)
+	}
+}
+
+/// Groups the items (or rows) into pieces wrapped in the list (or table) tags (outermost first).
+/// An item larger than `max_chars` is split by words, and a row by cells, in their own item (or row).
+fn split_parts<'a>(
+	tags: &[&str],
+	parts: Vec<ElementRef<'a>>,
+	options: &ChunkOptions,
+	source_map: &SourceMap,
+) -> Vec<Block<'a>> {
+	let mut blocks = Vec::new();
+	let mut group: Vec<ElementRef> = Vec::new();
+	let mut group_len = 0;
+	for part in parts {
+		let text = text_from_node(*part, &options.text_options);
+		let len = text.chars().count();
+		if !group.is_empty() && group_len + len > options.max_chars {
+			blocks.push(group_block(tags, &std::mem::take(&mut group), options, source_map));
+			group_len = 0;
+		}
+		if len > options.max_chars {
+			let part_tags = [tags, &[part.value().name()]].concat();
+			let cells: Vec<ElementRef> = if part.value().name() == "tr" {
+				part.children().filter_map(ElementRef::wrap).collect()
+			} else {
+				Vec::new()
+			};
+			if cells.is_empty() {
+				blocks.extend(split_text(&text, source_map.get(part.id()), &part_tags, options));
+			} else {
+				blocks.extend(split_parts(&part_tags, cells, options, source_map));
+			}
+			continue;
+		}
+		group.push(part);
+		group_len += len;
+	}
+	if !group.is_empty() {
+		blocks.push(group_block(tags, &group, options, source_map));
+	}
+
+	blocks
+}
+
+/// The items (or rows, cells) wrapped in the tags (outermost first).
+fn group_block<'a>(
+	tags: &[&str],
+	group: &[ElementRef<'a>],
+	options: &ChunkOptions,
+	source_map: &SourceMap,
+) -> Block<'a> {
+	Block {
+		html: wrap_html(tags, &group.iter().map(|part| part.html()).collect::<String>()),
+		text: text_from_nodes(group.iter().map(|part| **part), &options.text_options),
+		span: union_spans(group.iter().map(|part| source_map.get(part.id()))),
+		element: None,
+	}
+}
+
+/// Splits the text by lines (`<pre>`) or words, each piece wrapped in the tags (outermost first).
+fn split_text<'a>(text: &str, span: Option<SourceSpan>, tags: &[&str], options: &ChunkOptions) -> Vec<Block<'a>> {
+	let is_pre = tags.last() == Some(&"pre");
+	let units: Vec<&str> = if is_pre {
+		text.split_inclusive('\n').collect()
+	} else {
+		text.split_inclusive(char::is_whitespace).collect()
+	};
+
+	let mut pieces: Vec<String> = Vec::new();
+	let mut piece_len = 0;
+	for unit in units {
+		let len = unit.chars().count();
+		match pieces.last_mut() {
+			Some(piece) if piece_len + len <= options.max_chars => piece.push_str(unit),
+			_ => {
+				pieces.push(unit.to_string());
+				piece_len = 0;
+			}
+		}
+		piece_len += len;
+	}
+
+	pieces
+		.into_iter()
+		.map(|piece| {
+			if is_pre {
+				piece.trim_end_matches('\n').to_string()
+			} else {
+				piece.trim().to_string()
+			}
+		})
+		.filter(|text| !text.is_empty())
+		.map(|text| Block {
+			html: wrap_html(tags, &encode_text(&text)),
+			text,
+			span,
+			element: None,
+		})
+		.collect()
+}
+
+/// The rows of a table (not of its nested tables), in order.
+fn table_rows(table: ElementRef) -> Vec<ElementRef> {
+	let mut rows = Vec::new();
+	for child in table.children().filter_map(ElementRef::wrap) {
+		match child.value().name() {
+			"tr" => rows.push(child),
+			"thead" | "tbody" | "tfoot" => rows.extend(
+				child
+					.children()
+					.filter_map(ElementRef::wrap)
+					.filter(|row| row.value().name() == "tr"),
+			),
+			_ => (),
+		}
+	}
+	rows
+}
+
+// endregion: --- Split
+
+// region:    --- Support
+
+/// Whether the tag starts a block (`<br>` only breaks the line of an inline run).
+fn is_block(tag: &str) -> bool {
+	is_text_block_tag(tag) && tag != "br"
+}
+
+fn has_block_child(el: ElementRef) -> bool {
+	el.children()
+		.filter_map(ElementRef::wrap)
+		.any(|child| is_block(child.value().name()) && !NON_CONTENT_TAGS.contains(&child.value().name()))
+}
+
+/// The level of a `<h1>` to `<h6>` tag.
+fn heading_level(tag: &str) -> Option<usize> {
+	match tag.as_bytes() {
+		[b'h', level @ b'1'..=b'6'] => Some((level - b'0') as usize),
+		_ => None,
+	}
+}
+
+/// The HTML wrapped in the tags (outermost first).
+fn wrap_html(tags: &[&str], html: &str) -> String {
+	let open: String = tags.iter().map(|tag| format!("<{tag}>")).collect();
+	let close: String = tags.iter().rev().map(|tag| format!("</{tag}>")).collect();
+	format!("{open}{html}{close}")
+}
+
+fn node_html(node: NodeRef<Node>) -> String {
+	match node.value() {
+		Node::Text(text) => encode_text(&text.text).to_string(),
+		_ => ElementRef::wrap(node).map(|el| el.html()).unwrap_or_default(),
+	}
+}
+
+/// The span from the start of the first span to the furthest end (the spans are in document order).
+fn union_spans(spans: impl Iterator<Item = Option<SourceSpan>>) -> Option<SourceSpan> {
+	spans.flatten().reduce(|first, span| SourceSpan {
+		end: first.end.max(span.end),
+		..first
+	})
+}
+
+// endregion: --- Support
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	type TestResult<T> = core::result::Result<T, Box<dyn std::error::Error>>;
+
+	use super::*;
+
+	#[test]
+	fn test_chunker_chunk_headings_and_sections() -> TestResult<()> {
+		// -- Setup & Fixtures
+		let fx_html = r#"<body>
+<h1>Guide</h1>
+<p>Intro.</p>
+<h2>Install</h2>
+<p>Run the <code>install</code> command.</p>
+<ul><li>Linux</li><li>macOS</li></ul>
+<h3>Details</h3>
+<div>Some <b>inline</b> text<p>And a paragraph.</p></div>
+<h2>Usage</h2>
+<section><h4>Example</h4><pre>let a = 1;</pre></section>
+<p>After the section.</p>
+</body>"#;
+
+		// -- Exec
+		let chunks = chunk(fx_html, ChunkOptions::default())?;
+
+		// -- Check
+		let summary: Vec<(Vec<&str>, &str)> = chunks
+			.iter()
+			.map(|c| (c.breadcrumb.iter().map(String::as_str).collect(), c.text.as_str()))
+			.collect();
+		assert_eq!(
+			summary,
+			vec![
+				(vec!["Guide"], "Guide\n\nIntro."),
+				(
+					vec!["Guide", "Install"],
+					"Install\n\nRun the install command.\n\nLinux\nmacOS"
+				),
+				(
+					vec!["Guide", "Install", "Details"],
+					"Details\n\nSome inline text\n\nAnd a paragraph."
+				),
+				(vec!["Guide", "Usage"], "Usage"),
+				(vec!["Guide", "Usage", "Example"], "Example\n\nlet a = 1;"),
+				(vec!["Guide", "Usage"], "After the section."),
+			]
+		);
+
+		let install = &chunks[1];
+		assert_eq!(
+			install.html,
+			"<h2>Install</h2>\n<p>Run the <code>install</code> command.</p>\n<ul><li>Linux</li><li>macOS</li></ul>"
+		);
+		let span = install.span.ok_or("Should have a span")?;
+		assert!(fx_html[span.start..span.end].starts_with("<h2>Install</h2>"));
+		assert!(fx_html[span.start..span.end].ends_with("</ul>"));
+		assert_eq!(span.line, 4);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_chunker_chunk_max_chars_and_oversized() -> TestResult<()> {
+		// -- Setup & Fixtures
+		let fx_html = r#"<h1>Title</h1>
+<p>First paragraph here.</p>
+<p>Second paragraph here.</p>
+<ul><li>Item number one</li><li>Item number two</li><li>Item number three</li></ul>
+<pre>line one
+line two
+line three</pre>"#;
+
+		// -- Exec
+		let chunks = chunk(fx_html, ChunkOptions::default().with_max_chars(40))?;
+
+		// -- Check
+		let htmls: Vec<&str> = chunks.iter().map(|c| c.html.as_str()).collect();
+		assert_eq!(
+			htmls,
+			vec![
+				"<h1>Title</h1>\n<p>First paragraph here.</p>",
+				"<p>Second paragraph here.</p>",
+				"<ul><li>Item number one</li><li>Item number two</li></ul>",
+				"<ul><li>Item number three</li></ul>",
+				"<pre>line one\nline two\nline three</pre>",
+			]
+		);
+		assert!(chunks.iter().all(|c| c.breadcrumb == vec!["Title"]));
+		let item_three = chunks[3].span.ok_or("Should have a span")?;
+		assert_eq!(&fx_html[item_three.start..item_three.end], "<li>Item number three</li>");
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_chunker_chunk_oversized_single_parts() -> TestResult<()> {
+		// -- Setup & Fixtures
+		let fx_html = r#"<ul><li>alpha <b>beta</b> gamma delta epsilon</li></ul>
+<table><tr><td>one two</td><td>three four five six seven</td></tr></table>
+<pre>a = 1;
+b = 2;
+c = 3;
+d = 4;</pre>"#;
+
+		// -- Exec
+		let chunks = chunk(fx_html, ChunkOptions::default().with_max_chars(20))?;
+
+		// -- Check
+		let htmls: Vec<&str> = chunks.iter().map(|c| c.html.as_str()).collect();
+		assert_eq!(
+			htmls,
+			vec![
+				"<ul><li>alpha beta gamma</li></ul>",
+				"<ul><li>delta epsilon</li></ul>",
+				"<table><tr><td>one two</td></tr></table>",
+				"<table><tr><td>three four five six</td></tr></table>",
+				"<table><tr><td>seven</td></tr></table>",
+				"<pre>a = 1;\nb = 2;</pre>",
+				"<pre>c = 3;\nd = 4;</pre>",
+			]
+		);
+		let item = chunks[0].span.ok_or("Should have a span")?;
+		assert!(fx_html[item.start..item.end].starts_with("<li>alpha"));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_chunker_chunk_deep_nesting() -> TestResult<()> {
+		// -- Setup & Fixtures
+		let fx_html = format!(
+			"<h1>Title</h1>{}<p>Deep</p>{}<p>After</p>",
+			"<div>".repeat(2000),
+			"</div>".repeat(2000)
+		);
+
+		// -- Exec
+		let chunks = chunk(&fx_html, ChunkOptions::default())?;
+
+		// -- Check
+		let texts: Vec<&str> = chunks.iter().map(|c| c.text.as_str()).collect();
+		assert_eq!(texts, vec!["Title\n\nDeep\n\nAfter"]);
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
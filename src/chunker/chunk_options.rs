@@ -0,0 +1,53 @@
+use crate::TextOptions;
+
+// region:    --- Types
+
+/// Options for the `chunk` function.
+#[derive(Clone, Debug)]
+pub struct ChunkOptions {
+	/// The maximum size of a chunk, in characters of its text (default: 2000).
+	///
+	/// A single oversized block is split: lists by items, tables by rows, and other blocks by lines or words.
+	pub max_chars: usize,
+	/// The deepest heading level starting a chunk (default: 6). The deeper headings stay inside their chunk,
+	/// and are not part of the breadcrumbs.
+	pub max_heading_level: u8,
+	/// How `Chunk::text` is rendered (see `to_text`).
+	pub text_options: TextOptions,
+}
+
+impl Default for ChunkOptions {
+	fn default() -> Self {
+		Self {
+			max_chars: 2000,
+			max_heading_level: 6,
+			text_options: TextOptions::default(),
+		}
+	}
+}
+
+// endregion: --- Types
+
+// region:    --- Constructors & Fluid API
+
+impl ChunkOptions {
+	/// Set the maximum size of a chunk (in text characters).
+	pub fn with_max_chars(mut self, max_chars: usize) -> Self {
+		self.max_chars = max_chars;
+		self
+	}
+
+	/// Set the deepest heading level starting a chunk (e.g., `2` to split on `<h1>` and `<h2>` only).
+	pub fn with_max_heading_level(mut self, level: u8) -> Self {
+		self.max_heading_level = level;
+		self
+	}
+
+	/// Set how the chunk text is rendered.
+	pub fn with_text_options(mut self, text_options: TextOptions) -> Self {
+		self.text_options = text_options;
+		self
+	}
+}
+
+// endregion: --- Constructors & Fluid API
@@ -0,0 +1,9 @@
+// region:    --- Modules
+
+mod chunk;
+mod chunk_options;
+
+pub use chunk::*;
+pub use chunk_options::*;
+
+// endregion: --- Modules
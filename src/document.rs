@@ -0,0 +1,296 @@
+use crate::source::SourceMap;
+use crate::{
+	Chunk, ChunkOptions, Elem, ElemGroup, Form, Link, LinksOptions, MainContent, MainContentOptions, MarkdownOptions,
+	OutlineHeading, PageMetadata, Result, Schema, SelectOptions, SlimOptions, SlimOutput, SlimReport, Table,
+	TextOptions, chunks_from_document, extract_from_html, extract_main_content_from_html, forms_from_html,
+	links_from_html, markdown_from_node, metadata_from_html, outline_from_html, select_from_document,
+	select_grouped_from_document, slim_html, slim_html_mapped, slim_html_report, tables_from_document, text_from_node,
+};
+use html5ever::tendril::TendrilSink;
+use html5ever::{LocalName, QualName, driver, ns};
+use scraper::{ElementRef, Html, HtmlTreeSink};
+use serde_json::Value;
+use std::sync::OnceLock;
+
+// region:    --- Types
+
+/// A parsed HTML document, to run several operations (selections, slim, text, etc.) with a single parse.
+///
+/// The free functions (`select`, `slim`, `to_text`, `to_markdown`) are thin wrappers parsing a `Document`
+/// on each call, so prefer a `Document` when running many selectors over the same page.
+///
+/// The document borrows the HTML content (for the `SourceSpan`), so it cannot outlive it.
+#[derive(Debug, Clone)]
+pub struct Document<'a> {
+	html: Html,
+	/// Whether `html` was parsed as a fragment (its root `<html>` element is synthetic).
+	is_fragment: bool,
+	/// The HTML source, for the `SourceSpan` (see `SelectOptions::with_source_spans`).
+	source: &'a str,
+	/// Built on first use.
+	source_map: OnceLock<SourceMap>,
+}
+
+/// How the HTML content is parsed.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum ParseMode {
+	/// A full document: missing `<html>`, `<head>`, and `<body>` are created by the parser.
+	#[default]
+	Document,
+	/// A snippet (e.g., CMS content, email body), parsed with the HTML fragment parsing algorithm
+	/// in the context of the given element (`None` for `<body>`).
+	///
+	/// The context changes how the snippet is parsed, for example, `<td>` cells are only kept
+	/// in a `tr` context (they are dropped in a `body` context).
+	Fragment(Option<String>),
+}
+
+// endregion: --- Types
+
+// region:    --- Constructors
+
+impl<'a> Document<'a> {
+	/// Parses the HTML content as a full document.
+	pub fn parse(html_content: &'a str) -> Self {
+		Self::new(html_content, Html::parse_document(html_content), false)
+	}
+
+	/// Parses the HTML content as a fragment, in the context of the given element (`None` for `<body>`).
+	///
+	/// The operations do not see (or output) any synthetic `<html>`, `<head>`, or `<body>`.
+	pub fn parse_fragment(html_content: &'a str, context: Option<&str>) -> Self {
+		let html = match context {
+			Some(context) => parse_fragment_in_context(html_content, context),
+			None => Html::parse_fragment(html_content),
+		};
+		Self::new(html_content, html, true)
+	}
+
+	/// Parses the HTML content with the given `ParseMode`.
+	pub fn parse_with_mode(html_content: &'a str, parse_mode: &ParseMode) -> Self {
+		match parse_mode {
+			ParseMode::Document => Self::parse(html_content),
+			ParseMode::Fragment(context) => Self::parse_fragment(html_content, context.as_deref()),
+		}
+	}
+
+	fn new(html_content: &'a str, html: Html, is_fragment: bool) -> Self {
+		Self {
+			html,
+			is_fragment,
+			source: html_content,
+			source_map: OnceLock::new(),
+		}
+	}
+}
+
+impl ParseMode {
+	/// A fragment in a `<body>` context.
+	pub fn fragment() -> Self {
+		ParseMode::Fragment(None)
+	}
+
+	/// A fragment in the context of the given element (e.g., `"tr"`, `"ul"`, `"select"`).
+	pub fn fragment_in(context: impl Into<String>) -> Self {
+		ParseMode::Fragment(Some(context.into()))
+	}
+}
+
+/// Same as `Html::parse_fragment`, with a custom context element (HTML namespace).
+fn parse_fragment_in_context(html_content: &str, context: &str) -> Html {
+	let context_name = QualName::new(None, ns!(html), LocalName::from(context.trim().to_ascii_lowercase()));
+	let parser = driver::parse_fragment(
+		HtmlTreeSink::new(Html::new_fragment()),
+		Default::default(),
+		context_name,
+		Vec::new(),
+		false,
+	);
+	parser.one(html_content)
+}
+
+// endregion: --- Constructors
+
+// region:    --- Operations
+
+impl Document<'_> {
+	/// Selects elements matching the CSS selectors (see `select`).
+	pub fn select<S>(&self, selectors: S) -> Result<Vec<Elem>>
+	where
+		S: IntoIterator,
+		S::Item: AsRef<str>,
+	{
+		select_from_document(self, selectors, &SelectOptions::default())
+	}
+
+	/// Selects elements matching the CSS selectors, with `SelectOptions` (see `select_with_options`).
+	pub fn select_with_options<S>(&self, selectors: S, options: impl Into<SelectOptions>) -> Result<Vec<Elem>>
+	where
+		S: IntoIterator,
+		S::Item: AsRef<str>,
+	{
+		select_from_document(self, selectors, &options.into())
+	}
+
+	/// Selects elements grouped by selector (see `select_grouped`).
+	pub fn select_grouped<S>(&self, selectors: S) -> Result<Vec<ElemGroup>>
+	where
+		S: IntoIterator,
+		S::Item: AsRef<str>,
+	{
+		select_grouped_from_document(self, selectors, &SelectOptions::default())
+	}
+
+	/// Selects elements grouped by selector, with `SelectOptions`.
+	pub fn select_grouped_with_options<S>(
+		&self,
+		selectors: S,
+		options: impl Into<SelectOptions>,
+	) -> Result<Vec<ElemGroup>>
+	where
+		S: IntoIterator,
+		S::Item: AsRef<str>,
+	{
+		select_grouped_from_document(self, selectors, &options.into())
+	}
+
+	/// Slims the document (see `slim`).
+	pub fn slim(&self, options: impl Into<SlimOptions>) -> Result<String> {
+		slim_html(&self.html, &options.into())
+	}
+
+	/// Slims the document, with the output mappings back to the HTML source (see `slim_mapped`).
+	pub fn slim_mapped(&self, options: impl Into<SlimOptions>) -> Result<SlimOutput> {
+		slim_html_mapped(&self.html, &options.into(), Some(self.source_map()))
+	}
+
+	/// Slims the document, with the token budget report (see `slim_with_report`).
+	pub fn slim_with_report(&self, options: impl Into<SlimOptions>) -> Result<SlimReport> {
+		slim_html_report(&self.html, &options.into())
+	}
+
+	/// Renders the document as plain text (see `to_text`).
+	pub fn text(&self, options: impl Into<TextOptions>) -> String {
+		text_from_node(self.html.tree.root(), &options.into())
+	}
+
+	/// Extracts the data described by the schema (see `extract`).
+	pub fn extract(&self, schema: &Schema) -> Result<Value> {
+		extract_from_html(&self.html, schema)
+	}
+
+	/// Extracts the main content of the document (see `extract_main_content`).
+	pub fn extract_main_content(&self, options: impl Into<MainContentOptions>) -> Result<Option<MainContent>> {
+		extract_main_content_from_html(&self.html, &options.into())
+	}
+
+	/// Extracts the page metadata (see `extract_metadata`).
+	pub fn metadata(&self) -> PageMetadata {
+		metadata_from_html(&self.html)
+	}
+
+	/// Extracts the heading tree of the document (see `outline`).
+	pub fn outline(&self) -> Vec<OutlineHeading> {
+		outline_from_html(&self.html)
+	}
+
+	/// Splits the document into chunks by headings (see `chunk`).
+	pub fn chunk(&self, options: impl Into<ChunkOptions>) -> Vec<Chunk> {
+		chunks_from_document(self, &options.into())
+	}
+
+	/// Extracts the forms of the document (see `extract_forms`).
+	pub fn forms(&self) -> Vec<Form> {
+		forms_from_html(&self.html)
+	}
+
+	/// Extracts the links of the document (see `extract_links`).
+	pub fn links(&self, options: impl Into<LinksOptions>) -> Vec<Link> {
+		links_from_html(&self.html, &options.into())
+	}
+
+	/// Extracts the tables matching the CSS selectors (see `extract_tables`).
+	pub fn tables<S>(&self, selectors: S) -> Result<Vec<Table>>
+	where
+		S: IntoIterator,
+		S::Item: AsRef<str>,
+	{
+		tables_from_document(self, selectors)
+	}
+
+	/// Converts the document to Markdown (see `to_markdown`).
+	pub fn to_markdown(&self, options: impl Into<MarkdownOptions>) -> String {
+		markdown_from_node(self.html.tree.root(), &options.into())
+	}
+}
+
+// endregion: --- Operations
+
+// region:    --- Accessors
+
+impl Document<'_> {
+	/// The underlying `scraper::Html`.
+	pub(crate) fn html(&self) -> &Html {
+		&self.html
+	}
+
+	/// Whether the element is the synthetic `<html>` root of a fragment (not part of the content).
+	pub(crate) fn is_synthetic_root(&self, el: &ElementRef) -> bool {
+		self.is_fragment
+			&& el
+				.parent()
+				.is_some_and(|parent| parent.id() == self.html.tree.root().id())
+	}
+
+	/// The source positions of the elements (built on first call).
+	pub(crate) fn source_map(&self) -> &SourceMap {
+		self.source_map.get_or_init(|| SourceMap::new(self.source, &self.html))
+	}
+}
+
+// endregion: --- Accessors
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	type TestResult<T> = core::result::Result<T, Box<dyn std::error::Error>>;
+
+	use super::*;
+
+	#[test]
+	fn test_document_parse_once_multiple_operations() -> TestResult<()> {
+		// -- Setup & Fixtures
+		let fx_html = r#"
+		<html>
+		<head><title>Doc</title><script>x()</script></head>
+		<body>
+			<h1>Title</h1>
+			<ul><li>A</li><li>B</li></ul>
+			<a href="/x" class="link">Link</a>
+		</body>
+		</html>
+		"#;
+
+		// -- Exec
+		let doc = Document::parse(fx_html);
+		let h1s = doc.select(["h1"])?;
+		let links = doc.select(["a.link"])?;
+		let slimmed = doc.slim(SlimOptions::default())?;
+		let text = doc.text(TextOptions::default());
+		let md = doc.to_markdown(MarkdownOptions::default());
+
+		// -- Check
+		assert_eq!(h1s.len(), 1);
+		assert_eq!(h1s[0].text.as_deref(), Some("Title"));
+		assert_eq!(links.len(), 1);
+		assert!(!slimmed.contains("<script>"));
+		assert!(slimmed.contains("<h1>Title</h1>"));
+		assert_eq!(text, "Title\n\nA\nB\nLink");
+		assert!(md.starts_with("# Title"));
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
@@ -1,47 +1,304 @@
-use serde::Serialize;
-use std::collections::HashMap;
+use crate::source::SourceMap;
+use crate::url::resolve_url_attr;
+use crate::{Attrs, AttrsOrder, ElemTextMode, SelectOptions, SourceSpan, text_from_node};
+use scraper::ElementRef;
+use scraper::node::Node;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
 
 /// Represents a simplified HTML element, suitable for serialization.
-#[derive(Debug, Serialize)]
+///
+/// The JSON representation is stable (see `Elem::json_schema`), and round-trips with `serde_json`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Elem {
 	pub tag: String,
-	pub attrs: Option<HashMap<String, String>>,
+	/// The attributes, in source order by default (see `SelectOptions::with_attrs_order`).
+	pub attrs: Option<Attrs>,
 	pub text: Option<String>,
 	pub inner_html: Option<String>,
+	/// The element HTML, including its own tag (only with `SelectOptions::with_outer_html`).
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub outer_html: Option<String>,
+	/// The child nodes (only with `SelectOptions::with_children_depth`).
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub children: Option<Vec<ElemNode>>,
+	/// The element location in the HTML source (only with `SelectOptions::with_source_spans`).
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub span: Option<SourceSpan>,
+}
+
+/// A child node of an `Elem` (the text nodes are kept as is, including the whitespace-only ones).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ElemNode {
+	/// Serialized as the `Elem` properties with `"type": "element"`.
+	Element(Elem),
+	/// Serialized as `{ "type": "text", "text": "..." }`.
+	Text { text: String },
 }
 
 impl Elem {
+	/// Returns the JSON Schema (draft 2020-12) of the `Elem` JSON representation.
+	///
+	/// Optional properties (`outer_html`, `children`, `span`) are omitted when not set.
+	pub fn json_schema() -> Value {
+		json!({
+			"$schema": "https://json-schema.org/draft/2020-12/schema",
+			"$id": "html-helpers/elem",
+			"$ref": "#/$defs/elem",
+			"$defs": {
+				"elem": {
+					"type": "object",
+					"properties": {
+						"tag": { "type": "string" },
+						"attrs": {
+							"type": ["object", "null"],
+							"additionalProperties": { "type": "string" }
+						},
+						"text": { "type": ["string", "null"] },
+						"inner_html": { "type": ["string", "null"] },
+						"outer_html": { "type": ["string", "null"] },
+						"children": {
+							"type": ["array", "null"],
+							"items": { "$ref": "#/$defs/node" }
+						},
+						"span": { "$ref": "#/$defs/span" }
+					},
+					"required": ["tag", "attrs", "text", "inner_html"]
+				},
+				"span": {
+					"type": "object",
+					"properties": {
+						"start": { "type": "integer", "minimum": 0 },
+						"end": { "type": "integer", "minimum": 0 },
+						"line": { "type": "integer", "minimum": 1 },
+						"column": { "type": "integer", "minimum": 1 }
+					},
+					"required": ["start", "end", "line", "column"]
+				},
+				"node": {
+					"oneOf": [
+						{
+							"allOf": [
+								{ "$ref": "#/$defs/elem" },
+								{
+									"properties": { "type": { "const": "element" } },
+									"required": ["type"]
+								}
+							]
+						},
+						{
+							"type": "object",
+							"properties": {
+								"type": { "const": "text" },
+								"text": { "type": "string" }
+							},
+							"required": ["type", "text"],
+							"additionalProperties": false
+						}
+					]
+				}
+			}
+		})
+	}
+
 	/// Creates a new `Elem` from a `scraper::ElementRef`.
-	pub(crate) fn from_element_ref(el_ref: scraper::ElementRef) -> Self {
+	///
+	/// The `source_map` is only used when `SelectOptions::source_spans` is set, and the URL attributes are
+	/// resolved against the `base_url` (the effective one of the document) when given.
+	///
+	/// The `Elem::children` tree is built with a stack (no recursion), so deep documents are safe.
+	pub(crate) fn from_element_ref(
+		el_ref: ElementRef,
+		options: &SelectOptions,
+		source_map: Option<&SourceMap>,
+		base_url: Option<&str>,
+	) -> Self {
+		let mut elem = Self::from_element_ref_flat(el_ref, options, source_map, base_url, true);
+		let Some(depth) = options.children_depth.filter(|depth| *depth > 0) else {
+			return elem;
+		};
+		elem.children = Some(Vec::new());
+
+		// The open elements, with their remaining children and the number of children levels still to build
+		let mut stack = vec![(elem, el_ref.children(), depth)];
+		loop {
+			let (_, children, depth) = stack.last_mut().expect("the selected element frame should exist");
+			let depth = *depth;
+			let node = match children.next() {
+				Some(child) => match (child.value(), ElementRef::wrap(child)) {
+					(_, Some(child_ref)) => {
+						let mut child_elem =
+							Self::from_element_ref_flat(child_ref, options, source_map, base_url, false);
+						if depth > 1 {
+							child_elem.children = Some(Vec::new());
+							stack.push((child_elem, child_ref.children(), depth - 1));
+							continue;
+						}
+						ElemNode::Element(child_elem)
+					}
+					(Node::Text(text), None) => ElemNode::Text { text: text.to_string() },
+					_ => continue,
+				},
+				None => {
+					let (elem, _, _) = stack.pop().expect("the element frame should exist");
+					if stack.is_empty() {
+						return elem;
+					}
+					ElemNode::Element(elem)
+				}
+			};
+
+			let (parent, _, _) = stack.last_mut().expect("the parent frame should exist");
+			parent.children.get_or_insert_default().push(node);
+		}
+	}
+
+	/// The `Elem` without its `children` (`is_selected` is false for the elements built as children).
+	/// The `inner_html`/`outer_html` of the children elements are only set with `SelectOptions::children_html`.
+	fn from_element_ref_flat(
+		el_ref: ElementRef,
+		options: &SelectOptions,
+		source_map: Option<&SourceMap>,
+		base_url: Option<&str>,
+		is_selected: bool,
+	) -> Self {
 		let el = el_ref.value();
 		let tag = el.name().to_string();
 
 		let attrs = if el.attrs().next().is_some() {
-			let attrs = el.attrs().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+			let mut attrs: Attrs = el
+				.attrs()
+				.map(|(name, value)| {
+					let resolved = base_url.and_then(|base_url| resolve_url_attr(name, value, base_url));
+					(name, resolved.unwrap_or_else(|| value.to_string()))
+				})
+				.collect();
+			if options.attrs_order == AttrsOrder::Sorted {
+				attrs.sort();
+			}
 			Some(attrs)
 		} else {
 			None
 		};
 
-		let full_text = el_ref.text().collect::<String>();
+		let full_text = match options.text_mode {
+			ElemTextMode::Raw => el_ref.text().collect::<String>(),
+			ElemTextMode::Rendered(text_options) => text_from_node(*el_ref, &text_options),
+		};
 		let text = if full_text.trim().is_empty() {
 			None
 		} else {
 			Some(full_text.to_string())
 		};
 
-		let html_content = el_ref.inner_html();
-		let inner_html = if html_content.trim().is_empty() {
-			None
+		let with_html = is_selected || options.children_html;
+		let inner_html = if with_html {
+			let html_content = el_ref.inner_html();
+			if html_content.trim().is_empty() {
+				None
+			} else {
+				Some(html_content.to_string())
+			}
 		} else {
-			Some(html_content.to_string())
+			None
 		};
 
+		let outer_html = (with_html && options.outer_html).then(|| el_ref.html());
+
+		let span = source_map
+			.filter(|_| options.source_spans)
+			.and_then(|source_map| source_map.get(el_ref.id()));
+
 		Elem {
 			tag,
 			attrs,
 			text,
 			inner_html,
+			outer_html,
+			children: None,
+			span,
+		}
+	}
+}
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	type TestResult<T> = core::result::Result<T, Box<dyn std::error::Error>>;
+
+	use super::*;
+	use crate::select_with_options;
+
+	const FX_HTML: &str = r#"<div><a title="T" href="/a" class="c">A <b>link</b></a></div>"#;
+
+	#[test]
+	fn test_elem_attrs_source_and_sorted_order() -> TestResult<()> {
+		// -- Exec
+		let source = select_with_options(FX_HTML, ["a"], SelectOptions::default())?;
+		let sorted = select_with_options(
+			FX_HTML,
+			["a"],
+			SelectOptions::default().with_attrs_order(AttrsOrder::Sorted),
+		)?;
+
+		// -- Check
+		let source_json = serde_json::to_string(&source[0].attrs)?;
+		assert_eq!(source_json, r#"{"title":"T","href":"/a","class":"c"}"#);
+		let sorted_json = serde_json::to_string(&sorted[0].attrs)?;
+		assert_eq!(sorted_json, r#"{"class":"c","href":"/a","title":"T"}"#);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_elem_serde_round_trip() -> TestResult<()> {
+		// -- Setup & Fixtures
+		let options = SelectOptions::default().with_outer_html(true).with_children_depth(2);
+		let elems = select_with_options(FX_HTML, ["div"], options)?;
+
+		// -- Exec
+		let json = serde_json::to_string(&elems)?;
+		let elems_back: Vec<Elem> = serde_json::from_str(&json)?;
+
+		// -- Check
+		assert_eq!(elems_back, elems);
+		assert_eq!(serde_json::to_string(&elems_back)?, json);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_elem_children_whitespace_and_deep() -> TestResult<()> {
+		// -- Setup & Fixtures
+		let fx_deep = format!(
+			"<div id=\"deep\">{}x{}</div>",
+			"<span>".repeat(1000),
+			"</span>".repeat(1000)
+		);
+		let options = SelectOptions::default().with_children_depth(usize::MAX);
+
+		// -- Exec
+		let spaced = select_with_options("<p><b>a</b> <i>b</i></p>", ["p"], options.clone())?;
+		let deep = select_with_options(&fx_deep, ["#deep"], options)?;
+
+		// -- Check
+		let children = spaced[0].children.as_ref().ok_or("Should have children")?;
+		assert_eq!(children.len(), 3);
+		assert_eq!(children[1], ElemNode::Text { text: " ".to_string() });
+		let mut levels = 0;
+		let mut elem = &deep[0];
+		while let Some(ElemNode::Element(child)) = elem.children.as_ref().and_then(|children| children.first()) {
+			assert!(child.inner_html.is_none(), "Children have no inner_html by default");
+			levels += 1;
+			elem = child;
 		}
+		assert_eq!(levels, 1000);
+		assert_eq!(elem.children, Some(vec![ElemNode::Text { text: "x".to_string() }]));
+
+		Ok(())
 	}
 }
+
+// endregion: --- Tests
@@ -10,6 +10,10 @@ pub enum Error {
 
 	#[display("Selector '{selector}' is invalid.\nCause: {cause}")]
 	SelectorParse { selector: String, cause: String },
+
+	#[display("{_0}")]
+	#[from]
+	SerdeJson(serde_json::Error),
 }
 
 // region:    --- Custom
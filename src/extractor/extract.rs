@@ -0,0 +1,252 @@
+use super::{Field, FieldSource, Schema, Transform};
+use crate::support::collapse_whitespace;
+use crate::{Document, Result, build_selector};
+use scraper::{ElementRef, Html, Selector};
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+
+/// Extracts the data described by the schema from the HTML content, as a `serde_json::Value`.
+///
+/// - Without a `Schema::selector`, the result is one object built from the whole document.
+/// - With a selector, the result is the object of the first matching element (or `null`),
+///   or an array of objects when `Schema::multiple` is true.
+///
+/// All the selectors are parsed once, before the extraction (invalid ones return `Error::SelectorParse`).
+pub fn extract(html_content: &str, schema: &Schema) -> Result<Value> {
+	Document::parse(html_content).extract(schema)
+}
+
+/// Extracts the data described by the schema, and deserializes it into `T`.
+pub fn extract_into<T: DeserializeOwned>(html_content: &str, schema: &Schema) -> Result<T> {
+	let value = extract(html_content, schema)?;
+	Ok(serde_json::from_value(value)?)
+}
+
+/// Extracts the data of an already parsed HTML (see `Document::extract`).
+pub(crate) fn extract_from_html(html: &Html, schema: &Schema) -> Result<Value> {
+	let root = CompiledField {
+		name: String::new(),
+		selector: compile_selector(schema.selector.as_deref())?,
+		source: FieldSource::Text,
+		multiple: schema.multiple,
+		transforms: Vec::new(),
+		fields: compile_fields(&schema.fields)?,
+		is_object: true,
+	};
+
+	let scope = html.root_element();
+	let value = match &root.selector {
+		// Without a root selector, the document itself is the object.
+		None => extract_object(scope, &root.fields),
+		Some(_) => extract_field(scope, &root),
+	};
+
+	Ok(value)
+}
+
+// region:    --- Compiled Schema
+
+/// A schema field with its selector parsed.
+struct CompiledField {
+	name: String,
+	selector: Option<Selector>,
+	source: FieldSource,
+	multiple: bool,
+	transforms: Vec<Transform>,
+	fields: Vec<CompiledField>,
+	is_object: bool,
+}
+
+fn compile_fields(fields: &[Field]) -> Result<Vec<CompiledField>> {
+	fields
+		.iter()
+		.map(|field| {
+			Ok(CompiledField {
+				name: field.name.clone(),
+				selector: compile_selector(field.selector.as_deref())?,
+				source: field.source.clone(),
+				multiple: field.multiple,
+				transforms: field.transforms.clone(),
+				fields: compile_fields(&field.fields)?,
+				is_object: !field.fields.is_empty(),
+			})
+		})
+		.collect()
+}
+
+fn compile_selector(selector: Option<&str>) -> Result<Option<Selector>> {
+	match selector {
+		Some(selector) => build_selector([selector]),
+		None => Ok(None),
+	}
+}
+
+// endregion: --- Compiled Schema
+
+// region:    --- Extraction
+
+/// Extracts the value of a field, relative to the `scope` element.
+fn extract_field(scope: ElementRef, field: &CompiledField) -> Value {
+	let matches: Vec<ElementRef> = match &field.selector {
+		Some(selector) => scope.select(selector).collect(),
+		None => vec![scope],
+	};
+
+	if field.multiple {
+		Value::Array(matches.into_iter().map(|el| extract_value(el, field)).collect())
+	} else {
+		matches
+			.into_iter()
+			.next()
+			.map(|el| extract_value(el, field))
+			.unwrap_or(Value::Null)
+	}
+}
+
+fn extract_value(el: ElementRef, field: &CompiledField) -> Value {
+	if field.is_object {
+		return extract_object(el, &field.fields);
+	}
+
+	let value = match &field.source {
+		FieldSource::Text => {
+			let text = el.text().collect::<String>();
+			Value::String(collapse_whitespace(&text).trim().to_string())
+		}
+		FieldSource::InnerHtml => Value::String(el.inner_html()),
+		FieldSource::Attr(name) => el.value().attr(name).map(Value::from).unwrap_or(Value::Null),
+	};
+
+	field
+		.transforms
+		.iter()
+		.fold(value, |value, transform| transform.apply(value))
+}
+
+fn extract_object(el: ElementRef, fields: &[CompiledField]) -> Value {
+	let mut obj = Map::new();
+	for field in fields {
+		obj.insert(field.name.clone(), extract_field(el, field));
+	}
+	Value::Object(obj)
+}
+
+// endregion: --- Extraction
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	type TestResult<T> = core::result::Result<T, Box<dyn std::error::Error>>;
+
+	use super::*;
+	use crate::Error;
+	use serde::Deserialize;
+	use serde_json::json;
+
+	const FX_HTML: &str = r#"
+	<html>
+	<head><title>Shop</title></head>
+	<body>
+		<h1>Products</h1>
+		<div class="card">
+			<h2> Red   Shoes </h2>
+			<a href="/p/1">View</a>
+			<span class="price">$1,299.00</span>
+			<ul class="tags"><li>new</li><li>sale</li></ul>
+		</div>
+		<div class="card">
+			<h2>Blue Hat</h2>
+			<a href="/p/2">View</a>
+			<span class="price">$25</span>
+		</div>
+	</body>
+	</html>
+	"#;
+
+	#[test]
+	fn test_extractor_extract_from_json_schema() -> TestResult<()> {
+		// -- Setup & Fixtures
+		let schema = Schema::from_json(
+			r#"{
+				"fields": [
+					{ "name": "heading", "selector": "h1", "transforms": ["uppercase"] },
+					{ "name": "products", "selector": ".card", "multiple": true, "fields": [
+						{ "name": "title", "selector": "h2" },
+						{ "name": "url", "selector": "a", "source": { "attr": "href" } },
+						{ "name": "price", "selector": ".price", "transforms": ["number"] },
+						{ "name": "tags", "selector": ".tags li", "multiple": true }
+					]}
+				]
+			}"#,
+		)?;
+
+		// -- Exec
+		let value = extract(FX_HTML, &schema)?;
+
+		// -- Check
+		let expected = json!({
+			"heading": "PRODUCTS",
+			"products": [
+				{ "title": "Red Shoes", "url": "/p/1", "price": 1299.0, "tags": ["new", "sale"] },
+				{ "title": "Blue Hat", "url": "/p/2", "price": 25.0, "tags": [] }
+			]
+		});
+		assert_eq!(value, expected);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_extractor_extract_into_struct() -> TestResult<()> {
+		// -- Setup & Fixtures
+		#[derive(Debug, Deserialize, PartialEq)]
+		struct Product {
+			title: String,
+			url: String,
+			price: f64,
+			missing: Option<String>,
+		}
+		let schema = Schema::new([
+			Field::text("title", "h2"),
+			Field::attr("url", "a", "href"),
+			Field::text("price", ".price").with_transform(Transform::Number),
+			Field::text("missing", ".missing"),
+		])
+		.with_selector(".card")
+		.with_multiple(true);
+
+		// -- Exec
+		let products: Vec<Product> = extract_into(FX_HTML, &schema)?;
+
+		// -- Check
+		assert_eq!(products.len(), 2);
+		assert_eq!(
+			products[1],
+			Product {
+				title: "Blue Hat".to_string(),
+				url: "/p/2".to_string(),
+				price: 25.0,
+				missing: None,
+			}
+		);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_extractor_extract_invalid_selector() -> TestResult<()> {
+		// -- Setup & Fixtures
+		let schema = Schema::new([Field::text("title", "h2[")]);
+
+		// -- Exec
+		let res = extract(FX_HTML, &schema);
+
+		// -- Check
+		assert!(matches!(res, Err(Error::SelectorParse { .. })));
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
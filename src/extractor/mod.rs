@@ -0,0 +1,11 @@
+// region:    --- Modules
+
+mod extract;
+mod schema;
+mod transform;
+
+pub use extract::*;
+pub use schema::*;
+pub use transform::*;
+
+// endregion: --- Modules
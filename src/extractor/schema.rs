@@ -0,0 +1,156 @@
+use super::Transform;
+use crate::Result;
+use serde::{Deserialize, Serialize};
+
+// region:    --- Types
+
+/// A declarative extraction schema, applied with `extract` (or `extract_into`).
+///
+/// Can be built in Rust, or loaded from JSON with `Schema::from_json`, for example:
+///
+/// ```json
+/// {
+///   "selector": ".card",
+///   "multiple": true,
+///   "fields": [
+///     { "name": "title", "selector": "h2" },
+///     { "name": "url", "selector": "a", "source": { "attr": "href" } },
+///     { "name": "price", "selector": ".price", "transforms": ["number"] }
+///   ]
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Schema {
+	/// The root elements selector. When `None`, the fields are extracted from the whole document.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub selector: Option<String>,
+	/// When true, the result is an array with one object per root element.
+	#[serde(default)]
+	pub multiple: bool,
+	/// The fields of the extracted object(s).
+	pub fields: Vec<Field>,
+}
+
+/// A field of an extraction schema.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Field {
+	/// The property name in the extracted object.
+	pub name: String,
+	/// The selector, relative to the parent element. When `None`, the parent element itself is used.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub selector: Option<String>,
+	/// Where the value comes from (ignored when `fields` is not empty).
+	#[serde(default)]
+	pub source: FieldSource,
+	/// When true, the value is an array with all the matches (otherwise the first match, or `null`).
+	#[serde(default)]
+	pub multiple: bool,
+	/// The transforms applied, in order, to each value.
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	pub transforms: Vec<Transform>,
+	/// Nested fields. When not empty, the value is an object extracted from the matched element.
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	pub fields: Vec<Field>,
+}
+
+/// Where the value of a field comes from.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FieldSource {
+	/// The element text, with the whitespace collapsed and trimmed (JSON: `"text"`).
+	#[default]
+	Text,
+	/// The element inner HTML (JSON: `"inner_html"`).
+	InnerHtml,
+	/// An attribute value (JSON: `{ "attr": "href" }`).
+	Attr(String),
+}
+
+// endregion: --- Types
+
+// region:    --- Schema Constructors & Fluid API
+
+impl Schema {
+	/// Creates a schema extracting the fields from the whole document.
+	pub fn new(fields: impl IntoIterator<Item = Field>) -> Self {
+		Self {
+			selector: None,
+			multiple: false,
+			fields: fields.into_iter().collect(),
+		}
+	}
+
+	/// Loads a schema from its JSON representation.
+	pub fn from_json(json: &str) -> Result<Self> {
+		Ok(serde_json::from_str(json)?)
+	}
+
+	/// Set the root elements selector.
+	pub fn with_selector(mut self, selector: impl Into<String>) -> Self {
+		self.selector = Some(selector.into());
+		self
+	}
+
+	/// Extract one object per root element (array result).
+	pub fn with_multiple(mut self, multiple: bool) -> Self {
+		self.multiple = multiple;
+		self
+	}
+}
+
+// endregion: --- Schema Constructors & Fluid API
+
+// region:    --- Field Constructors & Fluid API
+
+impl Field {
+	/// A field with the text of the first element matching the selector.
+	pub fn text(name: impl Into<String>, selector: impl Into<String>) -> Self {
+		Self::new(name, Some(selector.into()), FieldSource::Text)
+	}
+
+	/// A field with an attribute of the first element matching the selector.
+	pub fn attr(name: impl Into<String>, selector: impl Into<String>, attr: impl Into<String>) -> Self {
+		Self::new(name, Some(selector.into()), FieldSource::Attr(attr.into()))
+	}
+
+	/// A field with the inner HTML of the first element matching the selector.
+	pub fn inner_html(name: impl Into<String>, selector: impl Into<String>) -> Self {
+		Self::new(name, Some(selector.into()), FieldSource::InnerHtml)
+	}
+
+	/// A field with an object extracted from the first element matching the selector.
+	pub fn nested(
+		name: impl Into<String>,
+		selector: impl Into<String>,
+		fields: impl IntoIterator<Item = Field>,
+	) -> Self {
+		let mut field = Self::new(name, Some(selector.into()), FieldSource::Text);
+		field.fields = fields.into_iter().collect();
+		field
+	}
+
+	fn new(name: impl Into<String>, selector: Option<String>, source: FieldSource) -> Self {
+		Self {
+			name: name.into(),
+			selector,
+			source,
+			multiple: false,
+			transforms: Vec::new(),
+			fields: Vec::new(),
+		}
+	}
+
+	/// Extract all the matches (array value).
+	pub fn with_multiple(mut self, multiple: bool) -> Self {
+		self.multiple = multiple;
+		self
+	}
+
+	/// Append a transform.
+	pub fn with_transform(mut self, transform: Transform) -> Self {
+		self.transforms.push(transform);
+		self
+	}
+}
+
+// endregion: --- Field Constructors & Fluid API
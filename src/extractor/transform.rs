@@ -0,0 +1,120 @@
+use crate::support::collapse_whitespace;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// A transform applied to an extracted value.
+///
+/// String transforms leave non-string values unchanged.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Transform {
+	/// Trims the leading and trailing whitespace (JSON: `"trim"`).
+	Trim,
+	/// Collapses the whitespace runs into a single space (JSON: `"collapse_whitespace"`).
+	CollapseWhitespace,
+	/// JSON: `"lowercase"`.
+	Lowercase,
+	/// JSON: `"uppercase"`.
+	Uppercase,
+	/// Replaces all the occurrences of `from` with `to` (JSON: `{ "replace": { "from": "$", "to": "" } }`).
+	Replace { from: String, to: String },
+	/// Parses the first number of the string, ignoring the other characters (e.g., `"$1,299.50"` gives `1299.5`),
+	/// or `null` when there is none (JSON: `"number"`).
+	Number,
+}
+
+impl Transform {
+	pub(crate) fn apply(&self, value: Value) -> Value {
+		let Value::String(s) = value else {
+			return value;
+		};
+
+		match self {
+			Transform::Trim => Value::String(s.trim().to_string()),
+			Transform::CollapseWhitespace => Value::String(collapse_whitespace(&s)),
+			Transform::Lowercase => Value::String(s.to_lowercase()),
+			Transform::Uppercase => Value::String(s.to_uppercase()),
+			Transform::Replace { from, to } => {
+				if from.is_empty() {
+					Value::String(s)
+				} else {
+					Value::String(s.replace(from.as_str(), to))
+				}
+			}
+			Transform::Number => parse_number(&s).map(Value::from).unwrap_or(Value::Null),
+		}
+	}
+}
+
+/// Parses the first number of a string, ignoring the thousands separators (`,`).
+fn parse_number(s: &str) -> Option<f64> {
+	let mut start = s.find(|c: char| c.is_ascii_digit())?;
+	// Keep a decimal point (e.g., `.99`), and a minus sign, right before the first digit.
+	if s[..start].ends_with('.') {
+		start -= 1;
+	}
+	let negative = s[..start].ends_with('-');
+
+	let mut num = String::new();
+	if negative {
+		num.push('-');
+	}
+	let mut has_dot = false;
+	for c in s[start..].chars() {
+		match c {
+			'0'..='9' => num.push(c),
+			',' => continue,
+			'.' if !has_dot => {
+				has_dot = true;
+				num.push(c);
+			}
+			_ => break,
+		}
+	}
+
+	num.trim_end_matches('.').parse().ok()
+}
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	type TestResult<T> = core::result::Result<T, Box<dyn std::error::Error>>;
+
+	use super::*;
+
+	#[test]
+	fn test_transform_number() -> TestResult<()> {
+		// -- Check
+		assert_eq!(
+			Transform::Number.apply(Value::from("$1,299.50 USD")),
+			Value::from(1299.5)
+		);
+		assert_eq!(Transform::Number.apply(Value::from("-12 pts")), Value::from(-12.0));
+		assert_eq!(Transform::Number.apply(Value::from(".99")), Value::from(0.99));
+		assert_eq!(Transform::Number.apply(Value::from("$.99")), Value::from(0.99));
+		assert_eq!(Transform::Number.apply(Value::from("-.5")), Value::from(-0.5));
+		assert_eq!(Transform::Number.apply(Value::from("v1.2.3")), Value::from(1.2));
+		assert_eq!(Transform::Number.apply(Value::from("n/a")), Value::Null);
+		assert_eq!(Transform::Number.apply(Value::Null), Value::Null);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_transform_from_json() -> TestResult<()> {
+		// -- Setup & Fixtures
+		let json = r#"["trim", "lowercase", { "replace": { "from": " ", "to": "-" } }]"#;
+
+		// -- Exec
+		let transforms: Vec<Transform> = serde_json::from_str(json)?;
+		let value = transforms.iter().fold(Value::from("  Hello World "), |v, t| t.apply(v));
+
+		// -- Check
+		assert_eq!(value, Value::from("hello-world"));
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
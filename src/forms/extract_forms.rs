@@ -0,0 +1,476 @@
+use super::{Form, FormField, SelectOption};
+use crate::support::collapse_whitespace;
+use crate::{Document, Result};
+use ego_tree::NodeId;
+use scraper::node::Node;
+use scraper::{ElementRef, Html};
+use std::collections::HashMap;
+
+// region:    --- Constants
+
+const FORM_METHODS: &[&str] = &["get", "post", "dialog"];
+
+const FORM_ENCTYPES: &[&str] = &["application/x-www-form-urlencoded", "multipart/form-data", "text/plain"];
+
+/// The `<button>` types (a missing or invalid type is `submit`).
+const BUTTON_TYPES: &[&str] = &["submit", "reset", "button"];
+
+/// The `<input>` types (a missing or unknown type is `text`).
+#[rustfmt::skip]
+const INPUT_TYPES: &[&str] = &[
+	"text", "hidden", "search", "tel", "url", "email", "password", "date", "month", "week", "time",
+	"datetime-local", "number", "range", "color", "checkbox", "radio", "file", "submit", "image", "reset", "button",
+];
+
+// endregion: --- Constants
+
+/// Extracts the forms of a page, with their fields, in document order.
+///
+/// A field belongs to the form given by its `form="<id>"` attribute, else to its `<form>` ancestor.
+/// The fields without form are skipped.
+pub fn extract_forms(html_content: &str) -> Result<Vec<Form>> {
+	Ok(Document::parse(html_content).forms())
+}
+
+/// Extracts the forms of an already parsed HTML (see `Document::forms`).
+pub(crate) fn forms_from_html(html: &Html) -> Vec<Form> {
+	let elements: Vec<ElementRef> = html.root_element().descendants().filter_map(ElementRef::wrap).collect();
+
+	// -- The forms, and the label texts by `for` id
+	let mut forms: Vec<Form> = Vec::new();
+	let mut form_indexes: HashMap<NodeId, usize> = HashMap::new();
+	let mut form_ids: HashMap<&str, usize> = HashMap::new();
+	let mut labels_for: HashMap<&str, String> = HashMap::new();
+	for el in elements.iter() {
+		let element = el.value();
+		match element.name() {
+			"form" => {
+				if let Some(id) = element.id() {
+					form_ids.entry(id).or_insert(forms.len());
+				}
+				form_indexes.insert(el.id(), forms.len());
+				forms.push(Form {
+					id: attr(*el, "id"),
+					name: attr(*el, "name"),
+					action: attr(*el, "action"),
+					method: allowed_value(element.attr("method"), FORM_METHODS),
+					enctype: allowed_value(element.attr("enctype"), FORM_ENCTYPES),
+					fields: Vec::new(),
+				});
+			}
+			"label" => {
+				if let Some(for_id) = element.attr("for") {
+					labels_for.entry(for_id).or_insert_with(|| label_text(*el));
+				}
+			}
+			_ => (),
+		}
+	}
+
+	// -- The fields, added to their form
+	for el in elements.iter() {
+		if !matches!(el.value().name(), "input" | "select" | "textarea" | "button") {
+			continue;
+		}
+		let form_index = match el.value().attr("form") {
+			Some(form_id) => form_ids.get(form_id).copied(),
+			None => el
+				.ancestors()
+				.find(|ancestor| form_indexes.contains_key(&ancestor.id()))
+				.and_then(|form| form_indexes.get(&form.id()).copied()),
+		};
+		let Some(form) = form_index.and_then(|idx| forms.get_mut(idx)) else {
+			continue;
+		};
+		form.fields.push(form_field(*el, &labels_for));
+	}
+
+	forms
+}
+
+// region:    --- Field
+
+fn form_field(el: ElementRef, labels_for: &HashMap<&str, String>) -> FormField {
+	let element = el.value();
+	let tag = element.name();
+
+	let options = match tag {
+		"select" => select_options(el),
+		_ => Vec::new(),
+	};
+	let multiple = element.attr("multiple").is_some();
+
+	let (kind, value) = match tag {
+		"select" => {
+			let kind = if multiple { "select-multiple" } else { "select" };
+			let value = options
+				.iter()
+				.find(|option| option.selected)
+				.or(options.first().filter(|_| !multiple))
+				.map(|option| option.value.clone());
+			(kind.to_string(), value)
+		}
+		"textarea" => ("textarea".to_string(), Some(el.text().collect())),
+		"button" => (allowed_value(element.attr("type"), BUTTON_TYPES), attr(el, "value")),
+		_ => {
+			let kind = allowed_value(element.attr("type"), INPUT_TYPES);
+			let value = match element.attr("value") {
+				Some(value) => Some(value.to_string()),
+				None if matches!(kind.as_str(), "checkbox" | "radio") => Some("on".to_string()),
+				None => None,
+			};
+			(kind, value)
+		}
+	};
+
+	let label = element
+		.id()
+		.and_then(|id| labels_for.get(id).cloned())
+		.or_else(|| {
+			el.ancestors()
+				.filter_map(ElementRef::wrap)
+				.find(|ancestor| ancestor.value().name() == "label")
+				.map(label_text)
+		})
+		.filter(|text| !text.is_empty())
+		.or_else(|| attr(el, "aria-label"));
+
+	FormField {
+		tag: tag.to_string(),
+		name: attr(el, "name"),
+		id: attr(el, "id"),
+		value,
+		options,
+		checked: element.attr("checked").is_some(),
+		required: element.attr("required").is_some(),
+		disabled: element.attr("disabled").is_some() || is_in_disabled_fieldset(el),
+		label,
+		placeholder: attr(el, "placeholder"),
+		kind,
+	}
+}
+
+/// The `<option>` of a `<select>`, directly or in `<optgroup>`.
+fn select_options(select: ElementRef) -> Vec<SelectOption> {
+	let mut options = Vec::new();
+	for child in select.children().filter_map(ElementRef::wrap) {
+		match child.value().name() {
+			"option" => options.push(select_option(child, false)),
+			"optgroup" => {
+				let group_disabled = child.value().attr("disabled").is_some();
+				options.extend(
+					child
+						.children()
+						.filter_map(ElementRef::wrap)
+						.filter(|option| option.value().name() == "option")
+						.map(|option| select_option(option, group_disabled)),
+				);
+			}
+			_ => (),
+		}
+	}
+	options
+}
+
+fn select_option(option: ElementRef, group_disabled: bool) -> SelectOption {
+	let element = option.value();
+	let text = collapse_whitespace(&option.text().collect::<String>())
+		.trim()
+		.to_string();
+	SelectOption {
+		value: element
+			.attr("value")
+			.map(str::to_string)
+			.unwrap_or_else(|| text.clone()),
+		selected: element.attr("selected").is_some(),
+		disabled: group_disabled || element.attr("disabled").is_some(),
+		text,
+	}
+}
+
+// endregion: --- Field
+
+// region:    --- Support
+
+/// The label text, without the text of the fields it wraps (e.g., the options of a `<select>`).
+fn label_text(label: ElementRef) -> String {
+	let mut text = String::new();
+	for node in label.descendants() {
+		let Node::Text(node_text) = node.value() else {
+			continue;
+		};
+		let in_field = node
+			.ancestors()
+			.take_while(|ancestor| ancestor.id() != label.id())
+			.any(|ancestor| {
+				ancestor
+					.value()
+					.as_element()
+					.is_some_and(|el| matches!(el.name(), "select" | "textarea" | "button"))
+			});
+		if !in_field {
+			text.push_str(node_text);
+		}
+	}
+	collapse_whitespace(&text).trim().to_string()
+}
+
+/// Whether an ancestor `<fieldset>` is disabled (the fields of its first `<legend>` stay enabled).
+fn is_in_disabled_fieldset(el: ElementRef) -> bool {
+	let mut child_id = el.id();
+	for ancestor in el.ancestors() {
+		if let Some(element) = ancestor.value().as_element()
+			&& element.name() == "fieldset"
+			&& element.attr("disabled").is_some()
+		{
+			let first_legend = ancestor
+				.children()
+				.filter_map(ElementRef::wrap)
+				.find(|child| child.value().name() == "legend");
+			if first_legend.is_none_or(|legend| legend.id() != child_id) {
+				return true;
+			}
+		}
+		child_id = ancestor.id();
+	}
+	false
+}
+
+/// The lowercase value when allowed, else the first allowed one (the default).
+fn allowed_value(value: Option<&str>, allowed: &[&str]) -> String {
+	let value = value.map(|v| v.trim().to_ascii_lowercase()).unwrap_or_default();
+	if allowed.contains(&value.as_str()) {
+		value
+	} else {
+		allowed[0].to_string()
+	}
+}
+
+/// The trimmed attribute, when not empty.
+fn attr(el: ElementRef, name: &str) -> Option<String> {
+	el.value()
+		.attr(name)
+		.map(str::trim)
+		.filter(|value| !value.is_empty())
+		.map(str::to_string)
+}
+
+// endregion: --- Support
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	type TestResult<T> = core::result::Result<T, Box<dyn std::error::Error>>;
+
+	use super::*;
+
+	#[test]
+	fn test_forms_extract_forms() -> TestResult<()> {
+		// -- Setup & Fixtures
+		let fx_html = r#"
+		<form id="signup" action="/signup" method="POST" enctype="multipart/form-data">
+			<input type="hidden" name="token" value="abc">
+			<label for="email">Email <b>address</b></label>
+			<input id="email" name="email" type="email" required placeholder="you@example.com">
+			<label>Country
+				<select name="country">
+					<option value="">Choose</option>
+					<optgroup label="Europe" disabled><option>France</option></optgroup>
+					<option value="us" selected>United States</option>
+				</select>
+			</label>
+			<label><input type="checkbox" name="terms" checked> I agree</label>
+			<fieldset disabled><input name="promo" aria-label="Promo code"></fieldset>
+			<textarea name="bio">Hello</textarea>
+			<button>Sign up</button>
+		</form>
+		<input name="outside" form="signup">
+		<form><input name="q"></form>
+		"#;
+
+		// -- Exec
+		let forms = extract_forms(fx_html)?;
+
+		// -- Check
+		assert_eq!(forms.len(), 2);
+		let form = &forms[0];
+		assert_eq!(form.action.as_deref(), Some("/signup"));
+		assert_eq!(form.method, "post");
+		assert_eq!(form.enctype, "multipart/form-data");
+		let names: Vec<Option<&str>> = form.fields.iter().map(|f| f.name.as_deref()).collect();
+		assert_eq!(
+			names,
+			vec![
+				Some("token"),
+				Some("email"),
+				Some("country"),
+				Some("terms"),
+				Some("promo"),
+				Some("bio"),
+				None,
+				Some("outside")
+			]
+		);
+
+		let email = &form.fields[1];
+		assert_eq!(email.kind, "email");
+		assert!(email.required);
+		assert_eq!(email.label.as_deref(), Some("Email address"));
+
+		let country = &form.fields[2];
+		assert_eq!(country.kind, "select");
+		assert_eq!(country.value.as_deref(), Some("us"));
+		assert_eq!(country.label.as_deref(), Some("Country"));
+		assert_eq!(country.options.len(), 3);
+		assert_eq!(country.options[1].value, "France");
+		assert!(country.options[1].disabled);
+
+		let terms = &form.fields[3];
+		assert_eq!((terms.kind.as_str(), terms.value.as_deref()), ("checkbox", Some("on")));
+		assert!(terms.checked);
+		assert_eq!(terms.label.as_deref(), Some("I agree"));
+
+		let promo = &form.fields[4];
+		assert!(promo.disabled);
+		assert_eq!(promo.label.as_deref(), Some("Promo code"));
+
+		assert_eq!(form.fields[5].value.as_deref(), Some("Hello"));
+		assert_eq!(form.fields[6].kind, "submit");
+
+		let search = &forms[1];
+		assert_eq!((search.method.as_str(), search.action.as_deref()), ("get", None));
+		assert_eq!(search.enctype, "application/x-www-form-urlencoded");
+		assert_eq!(search.fields[0].kind, "text");
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_forms_extract_forms_button_types() -> TestResult<()> {
+		// -- Setup & Fixtures
+		let fx_html = r#"<form>
+			<button>Default</button>
+			<button type="">Empty</button>
+			<button type="bogus">Bogus</button>
+			<button type=" RESET ">Reset</button>
+			<button type="button" value="go">Button</button>
+			<input type="submit" value="Send">
+		</form>"#;
+
+		// -- Exec
+		let forms = extract_forms(fx_html)?;
+
+		// -- Check
+		let kinds: Vec<&str> = forms[0].fields.iter().map(|f| f.kind.as_str()).collect();
+		assert_eq!(kinds, vec!["submit", "submit", "submit", "reset", "button", "submit"]);
+		assert_eq!(forms[0].fields[4].value.as_deref(), Some("go"));
+		assert_eq!(forms[0].fields[5].value.as_deref(), Some("Send"));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_forms_extract_forms_input_types() -> TestResult<()> {
+		// -- Setup & Fixtures
+		let fx_html = r#"<form>
+			<input name="default">
+			<input type="" name="empty">
+			<input type="bogus" name="bogus">
+			<input type=" EMAIL " name="email">
+			<input type="datetime-local" name="when">
+			<input type="checkbox" name="check">
+		</form>"#;
+
+		// -- Exec
+		let forms = extract_forms(fx_html)?;
+
+		// -- Check
+		let kinds: Vec<&str> = forms[0].fields.iter().map(|f| f.kind.as_str()).collect();
+		assert_eq!(
+			kinds,
+			vec!["text", "text", "text", "email", "datetime-local", "checkbox"]
+		);
+		assert_eq!(forms[0].fields[5].value.as_deref(), Some("on"));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_forms_extract_forms_select_values() -> TestResult<()> {
+		// -- Setup & Fixtures
+		let fx_html = r#"<form>
+			<select name="first"><option>  Red  apple </option><option value="b">Blue</option></select>
+			<select name="selected"><option value="a">A</option><option value="b" selected>B</option></select>
+			<select name="multi" multiple><option value="a">A</option><option value="b">B</option></select>
+			<select name="multi-selected" multiple>
+				<option value="a">A</option><option value="b" selected>B</option><option value="c" selected>C</option>
+			</select>
+			<select name="empty"></select>
+		</form>"#;
+
+		// -- Exec
+		let forms = extract_forms(fx_html)?;
+
+		// -- Check
+		let fields = &forms[0].fields;
+		let values: Vec<(&str, Option<&str>)> = fields.iter().map(|f| (f.kind.as_str(), f.value.as_deref())).collect();
+		assert_eq!(
+			values,
+			vec![
+				("select", Some("Red apple")),
+				("select", Some("b")),
+				("select-multiple", None),
+				("select-multiple", Some("b")),
+				("select", None),
+			]
+		);
+		assert_eq!(fields[0].options[0].text, "Red apple");
+		let selected: Vec<bool> = fields[3].options.iter().map(|o| o.selected).collect();
+		assert_eq!(selected, vec![false, true, true]);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_forms_extract_forms_labels() -> TestResult<()> {
+		// -- Setup & Fixtures
+		let fx_html = r#"<form>
+			<label for="a">First label</label>
+			<label for="a">Second label</label>
+			<input id="a" name="a">
+			<label for="b">By id</label>
+			<label>Wrapping <input id="b" name="b"></label>
+			<label>Size <select name="size"><option>Small</option></select> (required)</label>
+			<label> </label>
+			<input name="aria" aria-label="From aria">
+			<label><span></span><input name="empty-label" aria-label="Fallback"></label>
+			<input name="none">
+		</form>"#;
+
+		// -- Exec
+		let forms = extract_forms(fx_html)?;
+
+		// -- Check
+		let labels: Vec<(Option<&str>, Option<&str>)> = forms[0]
+			.fields
+			.iter()
+			.map(|f| (f.name.as_deref(), f.label.as_deref()))
+			.collect();
+		assert_eq!(
+			labels,
+			vec![
+				(Some("a"), Some("First label")),
+				(Some("b"), Some("By id")),
+				(Some("size"), Some("Size (required)")),
+				(Some("aria"), Some("From aria")),
+				(Some("empty-label"), Some("Fallback")),
+				(Some("none"), None),
+			]
+		);
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
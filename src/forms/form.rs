@@ -0,0 +1,64 @@
+use serde::{Deserialize, Serialize};
+
+// region:    --- Types
+
+/// A `<form>` of a page, with its fields (see `extract_forms`).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Form {
+	/// The `id` attribute.
+	pub id: Option<String>,
+	/// The `name` attribute.
+	pub name: Option<String>,
+	/// The `action` attribute, as found in the page (`None` submits to the page URL).
+	pub action: Option<String>,
+	/// The lowercase `method` (`get` when missing or invalid, `post`, or `dialog`).
+	pub method: String,
+	/// The lowercase `enctype` (`application/x-www-form-urlencoded` when missing or invalid).
+	pub enctype: String,
+	/// The fields, in document order (including the ones outside the form with a `form="<id>"` attribute).
+	pub fields: Vec<FormField>,
+}
+
+/// A field of a `Form` (`<input>`, `<select>`, `<textarea>`, or `<button>`).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FormField {
+	/// The tag name (e.g., `input`, `select`).
+	pub tag: String,
+	/// The `name` attribute (the submitted key).
+	pub name: Option<String>,
+	/// The `id` attribute.
+	pub id: Option<String>,
+	/// The lowercase type: the `type` of inputs (`text` when missing or unknown) and buttons (`submit` when missing
+	/// or invalid), `select` (or `select-multiple`), or `textarea`.
+	#[serde(rename = "type")]
+	pub kind: String,
+	/// The value: the `value` attribute (`on` for checkboxes and radios without it), the text of a `<textarea>`,
+	/// or the value of the first selected option of a `<select>` (the first option for a single select).
+	pub value: Option<String>,
+	/// The options of a `<select>` (including the ones in `<optgroup>`).
+	pub options: Vec<SelectOption>,
+	/// Whether a checkbox or radio is `checked`.
+	pub checked: bool,
+	/// Whether the field is `required`.
+	pub required: bool,
+	/// Whether the field is `disabled` (itself or by a disabled `<fieldset>`).
+	pub disabled: bool,
+	/// The text of the associated `<label>` (`for` the field id, or wrapping it), else its `aria-label`.
+	pub label: Option<String>,
+	/// The `placeholder` attribute.
+	pub placeholder: Option<String>,
+}
+
+/// An `<option>` of a `<select>` field.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SelectOption {
+	/// The `value` attribute, or the option text.
+	pub value: String,
+	/// The whitespace collapsed text.
+	pub text: String,
+	pub selected: bool,
+	/// Whether the option (or its `<optgroup>`) is `disabled`.
+	pub disabled: bool,
+}
+
+// endregion: --- Types
@@ -0,0 +1,9 @@
+// region:    --- Modules
+
+mod extract_forms;
+mod form;
+
+pub use extract_forms::*;
+pub use form::*;
+
+// endregion: --- Modules
@@ -1,13 +1,42 @@
 // region:    --- Modules
 
+mod attrs;
+mod chunker;
+mod document;
 mod elem;
 mod error;
+mod extractor;
+mod forms;
+mod links;
+mod main_content;
+mod markdown;
+mod metadata;
+mod outline;
 mod selector;
 mod slimmer;
+mod source;
+mod support;
+mod tables;
+mod text;
+mod url;
 
+pub use attrs::*;
+pub use chunker::*;
+pub use document::*;
 pub use elem::*;
 pub use error::{Error, Result};
+pub use extractor::*;
+pub use forms::*;
+pub use links::*;
+pub use main_content::*;
+pub use markdown::*;
+pub use metadata::*;
+pub use outline::*;
 pub use selector::*;
 pub use slimmer::*;
+pub use source::*;
+pub use tables::*;
+pub use text::*;
+pub use url::*;
 
 // endregion: --- Modules
@@ -0,0 +1,269 @@
+use super::{Link, LinkKind, LinksOptions};
+use crate::support::collapse_whitespace;
+use crate::url::{effective_base_url, url_host, url_scheme};
+use crate::{Document, Result, resolve_url};
+use scraper::{ElementRef, Html};
+use std::collections::HashSet;
+
+/// Extracts the links of a page (anchors, `<link>`, `<area>`, and optionally `<img src>`), in document order.
+///
+/// The elements without (or with an empty) `href`/`src` are skipped. See `LinksOptions` for the URL resolution,
+/// deduplication, and scheme/domain filters.
+pub fn extract_links(html_content: &str, options: impl Into<LinksOptions>) -> Result<Vec<Link>> {
+	Ok(Document::parse(html_content).links(options))
+}
+
+/// Extracts the links of an already parsed HTML (see `Document::links`).
+pub(crate) fn links_from_html(html: &Html, options: &LinksOptions) -> Vec<Link> {
+	let base_url = options
+		.base_url
+		.as_deref()
+		.map(|base_url| effective_base_url(html, base_url));
+	let page_host = options.base_url.as_deref().and_then(url_host);
+
+	let mut seen: HashSet<(LinkKind, String)> = HashSet::new();
+	let mut links = Vec::new();
+
+	for el in html.root_element().descendants().filter_map(ElementRef::wrap) {
+		let element = el.value();
+		let (kind, url_attr) = match element.name() {
+			"a" => (LinkKind::Anchor, "href"),
+			"link" => (LinkKind::Link, "href"),
+			"area" => (LinkKind::Area, "href"),
+			"img" => (LinkKind::Image, "src"),
+			_ => continue,
+		};
+		if !options.kinds.contains(&kind) {
+			continue;
+		}
+		let Some(href) = element.attr(url_attr).map(str::trim).filter(|href| !href.is_empty()) else {
+			continue;
+		};
+
+		// Absolute hrefs are resolved (normalized) even without base URL
+		let resolved = resolve_url(base_url.as_deref().unwrap_or_default(), href);
+		let url = resolved.as_deref().unwrap_or(href);
+
+		// -- Filters
+		if !options.schemes.is_empty()
+			&& url_scheme(url).is_some_and(|scheme| !options.schemes.iter().any(|s| s.eq_ignore_ascii_case(&scheme)))
+		{
+			continue;
+		}
+		let host = url_host(url);
+		if !options.domains.is_empty()
+			&& !host
+				.as_deref()
+				.is_some_and(|host| is_in_domains(host, &options.domains))
+		{
+			continue;
+		}
+		if options.dedupe && !seen.insert((kind, url.to_string())) {
+			continue;
+		}
+
+		let is_external = match (&host, &page_host) {
+			(Some(host), Some(page_host)) => host != page_host,
+			(Some(_), None) => true,
+			_ => false,
+		};
+
+		links.push(Link {
+			href: href.to_string(),
+			text: link_text(el, kind),
+			rel: element
+				.attr("rel")
+				.map(|rel| rel.split_ascii_whitespace().map(str::to_ascii_lowercase).collect())
+				.unwrap_or_default(),
+			title: non_empty(element.attr("title")),
+			is_external,
+			is_fragment: href.starts_with('#'),
+			kind,
+			resolved,
+		});
+	}
+
+	links
+}
+
+// region:    --- Support
+
+/// The text of an anchor (falling back to its `aria-label` and image `alt`), or the `alt` of an area or image.
+fn link_text(el: ElementRef, kind: LinkKind) -> Option<String> {
+	match kind {
+		LinkKind::Anchor => {
+			let text = collapse_whitespace(&el.text().collect::<String>());
+			non_empty(Some(&text))
+				.or_else(|| non_empty(el.value().attr("aria-label")))
+				.or_else(|| {
+					el.descendants()
+						.filter_map(ElementRef::wrap)
+						.filter(|d| d.value().name() == "img")
+						.find_map(|img| non_empty(img.value().attr("alt")))
+				})
+		}
+		LinkKind::Area | LinkKind::Image => non_empty(el.value().attr("alt")),
+		LinkKind::Link => None,
+	}
+}
+
+/// Whether the host is one of the domains or one of their subdomains.
+fn is_in_domains(host: &str, domains: &[String]) -> bool {
+	domains.iter().any(|domain| {
+		let domain = domain.trim().trim_start_matches('.').to_ascii_lowercase();
+		host == domain || host.strip_suffix(&domain).is_some_and(|sub| sub.ends_with('.'))
+	})
+}
+
+fn non_empty(value: Option<&str>) -> Option<String> {
+	value.map(str::trim).filter(|v| !v.is_empty()).map(str::to_string)
+}
+
+// endregion: --- Support
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	type TestResult<T> = core::result::Result<T, Box<dyn std::error::Error>>;
+
+	use super::*;
+
+	const FX_HTML: &str = r##"
+	<html>
+	<head>
+		<link rel="stylesheet" href="/style.css">
+		<link rel="canonical" href="https://example.com/blog/post">
+	</head>
+	<body>
+		<nav><a href="/">Home</a> <a href="#content">Skip</a></nav>
+		<p>Read the <a href="../docs/intro.html" title="Intro"> getting
+			started </a> guide, or see <a href="https://Other.org/x" rel="nofollow noopener">this</a>.</p>
+		<a href="/"><img src="/logo.png" alt="Logo"></a>
+		<a href="https://docs.example.com/api">API</a>
+		<a href="mailto:me@example.com">Mail</a>
+		<a name="no-href">Anchor</a>
+		<map><area href="/area" alt="Area"></map>
+	</body>
+	</html>
+	"##;
+
+	#[test]
+	fn test_links_extract_links_resolved() -> TestResult<()> {
+		// -- Exec
+		let links = extract_links(
+			FX_HTML,
+			LinksOptions::default().with_base_url("https://example.com/blog/post"),
+		)?;
+
+		// -- Check
+		let summary: Vec<(LinkKind, &str, Option<&str>, bool)> = links
+			.iter()
+			.map(|l| (l.kind, l.href.as_str(), l.resolved.as_deref(), l.is_external))
+			.collect();
+		assert_eq!(
+			summary,
+			vec![
+				(
+					LinkKind::Link,
+					"/style.css",
+					Some("https://example.com/style.css"),
+					false
+				),
+				(
+					LinkKind::Link,
+					"https://example.com/blog/post",
+					Some("https://example.com/blog/post"),
+					false
+				),
+				(LinkKind::Anchor, "/", Some("https://example.com/"), false),
+				(
+					LinkKind::Anchor,
+					"#content",
+					Some("https://example.com/blog/post#content"),
+					false
+				),
+				(
+					LinkKind::Anchor,
+					"../docs/intro.html",
+					Some("https://example.com/docs/intro.html"),
+					false
+				),
+				(
+					LinkKind::Anchor,
+					"https://Other.org/x",
+					Some("https://Other.org/x"),
+					true
+				),
+				(
+					LinkKind::Anchor,
+					"https://docs.example.com/api",
+					Some("https://docs.example.com/api"),
+					true
+				),
+				(
+					LinkKind::Anchor,
+					"mailto:me@example.com",
+					Some("mailto:me@example.com"),
+					false
+				),
+				(LinkKind::Area, "/area", Some("https://example.com/area"), false),
+			]
+		);
+		assert!(links[3].is_fragment);
+		assert_eq!(links[4].text.as_deref(), Some("getting started"));
+		assert_eq!(links[4].title.as_deref(), Some("Intro"));
+		assert_eq!(links[5].rel, vec!["nofollow", "noopener"]);
+		assert_eq!(links[8].text.as_deref(), Some("Area"));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_links_extract_links_filters() -> TestResult<()> {
+		// -- Exec
+		let no_base = extract_links(
+			FX_HTML,
+			LinksOptions::default()
+				.with_kinds([LinkKind::Anchor, LinkKind::Image])
+				.with_dedupe(false),
+		)?;
+		let filtered = extract_links(
+			FX_HTML,
+			LinksOptions::default()
+				.with_base_url("https://example.com/blog/post")
+				.with_kinds([LinkKind::Anchor])
+				.with_schemes(["https"])
+				.with_domains(["example.com"]),
+		)?;
+
+		// -- Check
+		let hrefs: Vec<&str> = no_base.iter().map(|l| l.href.as_str()).collect();
+		assert_eq!(
+			hrefs,
+			vec![
+				"/",
+				"#content",
+				"../docs/intro.html",
+				"https://Other.org/x",
+				"/",
+				"/logo.png",
+				"https://docs.example.com/api",
+				"mailto:me@example.com",
+			]
+		);
+		assert_eq!(no_base[0].resolved, None);
+		assert!(!no_base[0].is_external && no_base[3].is_external);
+		assert_eq!(no_base[4].text.as_deref(), Some("Logo"));
+
+		let hrefs: Vec<&str> = filtered.iter().map(|l| l.href.as_str()).collect();
+		assert_eq!(
+			hrefs,
+			vec!["/", "#content", "../docs/intro.html", "https://docs.example.com/api"]
+		);
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
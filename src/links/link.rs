@@ -0,0 +1,40 @@
+use serde::{Deserialize, Serialize};
+
+// region:    --- Types
+
+/// A link of a page (see `extract_links`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Link {
+	/// The `href` (or `src` for images), trimmed.
+	pub href: String,
+	/// The absolute URL, when `LinksOptions::base_url` is set (or the `href` is absolute).
+	pub resolved: Option<String>,
+	/// The whitespace collapsed text (anchors), or the `alt` (areas, images, and image-only anchors).
+	pub text: Option<String>,
+	/// The lowercase `rel` tokens (e.g., `["nofollow", "noopener"]`).
+	pub rel: Vec<String>,
+	/// The `title` attribute.
+	pub title: Option<String>,
+	/// Whether the link points to another host than the base URL (any host without base URL).
+	pub is_external: bool,
+	/// Whether the link points to a fragment of the same page (e.g., `#section`).
+	pub is_fragment: bool,
+	/// The element of the link.
+	pub kind: LinkKind,
+}
+
+/// The element a `Link` comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LinkKind {
+	/// `<a href>`
+	Anchor,
+	/// `<link href>` (e.g., stylesheets, icons, alternates)
+	Link,
+	/// `<area href>` (image maps)
+	Area,
+	/// `<img src>`
+	Image,
+}
+
+// endregion: --- Types
@@ -0,0 +1,70 @@
+use super::LinkKind;
+
+// region:    --- Types
+
+/// Options for the `extract_links` function.
+#[derive(Clone, Debug)]
+pub struct LinksOptions {
+	/// The kinds of links extracted (default: anchors, `<link>`, and `<area>`, not images).
+	pub kinds: Vec<LinkKind>,
+	/// The absolute URL of the page, to set `Link::resolved` and `Link::is_external`. A `<base href>` of the document
+	/// is honored for the resolution.
+	pub base_url: Option<String>,
+	/// Whether the links with the same kind and URL (resolved if possible) are kept once, first wins (default: true).
+	pub dedupe: bool,
+	/// The schemes kept (e.g., `["http", "https"]`), all when empty. Relative links without base URL are always kept.
+	pub schemes: Vec<String>,
+	/// The domains kept, subdomains included (e.g., `example.com` keeps `docs.example.com`), all when empty.
+	/// The links without host (e.g., relative ones without base URL) are not kept.
+	pub domains: Vec<String>,
+}
+
+impl Default for LinksOptions {
+	fn default() -> Self {
+		Self {
+			kinds: vec![LinkKind::Anchor, LinkKind::Link, LinkKind::Area],
+			base_url: None,
+			dedupe: true,
+			schemes: Vec::new(),
+			domains: Vec::new(),
+		}
+	}
+}
+
+// endregion: --- Types
+
+// region:    --- Constructors & Fluid API
+
+impl LinksOptions {
+	/// Set the kinds of links extracted (e.g., `[LinkKind::Anchor, LinkKind::Image]`).
+	pub fn with_kinds(mut self, kinds: impl IntoIterator<Item = LinkKind>) -> Self {
+		self.kinds = kinds.into_iter().collect();
+		self
+	}
+
+	/// Set the page URL the links are resolved against.
+	pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+		self.base_url = Some(base_url.into());
+		self
+	}
+
+	/// Set whether the duplicate links are removed.
+	pub fn with_dedupe(mut self, dedupe: bool) -> Self {
+		self.dedupe = dedupe;
+		self
+	}
+
+	/// Set the schemes kept (case-insensitive, without `:`).
+	pub fn with_schemes(mut self, schemes: impl IntoIterator<Item = impl Into<String>>) -> Self {
+		self.schemes = schemes.into_iter().map(Into::into).collect();
+		self
+	}
+
+	/// Set the domains kept (case-insensitive, subdomains included).
+	pub fn with_domains(mut self, domains: impl IntoIterator<Item = impl Into<String>>) -> Self {
+		self.domains = domains.into_iter().map(Into::into).collect();
+		self
+	}
+}
+
+// endregion: --- Constructors & Fluid API
@@ -0,0 +1,11 @@
+// region:    --- Modules
+
+mod extract_links;
+mod link;
+mod links_options;
+
+pub use extract_links::*;
+pub use link::*;
+pub use links_options::*;
+
+// endregion: --- Modules
@@ -0,0 +1,240 @@
+use super::MainContentOptions;
+use super::scoring::{Scores, is_boilerplate, link_density, text_length};
+use crate::support::collapse_whitespace;
+use crate::{Document, Result, build_selector};
+use scraper::{ElementRef, Html};
+use serde::{Deserialize, Serialize};
+
+// region:    --- Types
+
+/// The main content of a page (see `extract_main_content`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MainContent {
+	/// The main content, slimmed with `MainContentOptions::slim_options`.
+	pub html: String,
+	/// The `og:title`, `<title>`, or first `<h1>` of the main content.
+	pub title: Option<String>,
+	/// The author (`<meta name="author">`, `rel="author"`, `itemprop="author"`, or `.byline`/`.author` element).
+	pub byline: Option<String>,
+	/// The publication date, as found in the page (`article:published_time`, `itemprop="datePublished"`,
+	/// or `<time datetime>`).
+	pub date: Option<String>,
+}
+
+// endregion: --- Types
+
+/// Extracts the main content ("article body") of a page, readability style.
+///
+/// The text blocks (`p`, `pre`, `td`, `blockquote`, and `div`s without block children) are scored by text length and
+/// commas, and their scores are added to their ancestors. The best ancestor, scaled by its link density, is the main
+/// content (with its siblings scoring close to it). Boilerplate is skipped: non-content tags, `nav`/`aside`/`footer`,
+/// landmark roles, hidden content, and elements whose `class`/`id` hints at comments, sidebars, menus, etc.
+///
+/// Returns `None` when no block has enough text (see `MainContentOptions::min_text_length`).
+pub fn extract_main_content(html_content: &str, options: impl Into<MainContentOptions>) -> Result<Option<MainContent>> {
+	Document::parse(html_content).extract_main_content(options)
+}
+
+/// Extracts the main content of an already parsed HTML (see `Document::extract_main_content`).
+pub(crate) fn extract_main_content_from_html(html: &Html, options: &MainContentOptions) -> Result<Option<MainContent>> {
+	let root = html.root_element();
+	let scope = match build_selector(["body"])? {
+		Some(selector) => root.select(&selector).next().unwrap_or(root),
+		None => root,
+	};
+
+	let scores = Scores::new(scope, options);
+	let Some((top, top_score)) = scores.top_candidate(scope) else {
+		return Ok(None);
+	};
+
+	// -- Gather the top candidate with its related siblings
+	let threshold = (top_score * 0.2).max(10.0);
+	let (content_html, context) = match top.parent().and_then(ElementRef::wrap) {
+		Some(parent) if top.id() != scope.id() => {
+			let content_html: String = parent
+				.children()
+				.filter_map(ElementRef::wrap)
+				.filter(|sibling| {
+					sibling.id() == top.id()
+						|| (!is_boilerplate(*sibling, options)
+							&& (scores.final_score(*sibling).is_some_and(|score| score >= threshold)
+								|| is_content_paragraph(*sibling)))
+				})
+				.map(|sibling| sibling.html())
+				.collect();
+			(content_html, Some(parent.value().name()))
+		}
+		_ => (top.inner_html(), None),
+	};
+
+	let html = Document::parse_fragment(&content_html, context).slim(options.slim_options.clone())?;
+
+	Ok(Some(MainContent {
+		html,
+		title: find_title(root, top)?,
+		byline: find_byline(root)?,
+		date: find_date(root, top)?,
+	}))
+}
+
+// region:    --- Support
+
+/// A sibling paragraph kept with the top candidate (long enough, few links).
+fn is_content_paragraph(el: ElementRef) -> bool {
+	el.value().name() == "p" && text_length(el) > 80 && link_density(el) < 0.25
+}
+
+fn find_title(root: ElementRef, top: ElementRef) -> Result<Option<String>> {
+	let title = meta_content(root, r#"meta[property="og:title"]"#)?
+		.or(first_text(root, "title")?)
+		.or(first_text(top, "h1")?);
+	Ok(title)
+}
+
+fn find_byline(root: ElementRef) -> Result<Option<String>> {
+	if let Some(author) = meta_content(root, r#"meta[name="author"]"#)? {
+		return Ok(Some(author));
+	}
+	let byline = first_text(root, r#"[rel="author"], [itemprop="author"], .byline, .author"#)?
+		.filter(|text| text.chars().count() <= 100);
+	Ok(byline)
+}
+
+fn find_date(root: ElementRef, top: ElementRef) -> Result<Option<String>> {
+	let date = meta_content(
+		root,
+		r#"meta[property="article:published_time"], meta[itemprop="datePublished"], meta[name="date"]"#,
+	)?
+	.or(first_attr(top, "time[datetime]", "datetime")?)
+	.or(first_attr(root, r#"[itemprop="datePublished"][datetime]"#, "datetime")?)
+	.or(first_attr(root, "time[datetime]", "datetime")?);
+	Ok(date)
+}
+
+fn meta_content(scope: ElementRef, selector: &str) -> Result<Option<String>> {
+	first_attr(scope, selector, "content")
+}
+
+/// The trimmed attribute of the first element matching the selector (when not empty).
+fn first_attr(scope: ElementRef, selector: &str, attr: &str) -> Result<Option<String>> {
+	let Some(selector) = build_selector([selector])? else {
+		return Ok(None);
+	};
+	let value = scope
+		.select(&selector)
+		.filter_map(|el| el.value().attr(attr))
+		.map(|value| value.trim().to_string())
+		.find(|value| !value.is_empty());
+	Ok(value)
+}
+
+/// The whitespace collapsed text of the first element matching the selector (when not empty).
+fn first_text(scope: ElementRef, selector: &str) -> Result<Option<String>> {
+	let Some(selector) = build_selector([selector])? else {
+		return Ok(None);
+	};
+	let text = scope
+		.select(&selector)
+		.map(|el| collapse_whitespace(&el.text().collect::<String>()).trim().to_string())
+		.find(|text| !text.is_empty());
+	Ok(text)
+}
+
+// endregion: --- Support
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	type TestResult<T> = core::result::Result<T, Box<dyn std::error::Error>>;
+
+	use super::*;
+
+	const FX_HTML: &str = r#"
+	<html>
+	<head>
+		<title>Rust Ownership Explained | Dev Blog</title>
+		<meta name="author" content="Jane Doe">
+		<meta property="article:published_time" content="2024-05-01T10:00:00Z">
+	</head>
+	<body>
+		<header><nav><a href="/">Home</a> <a href="/blog">Blog</a> <a href="/about">About</a></nav></header>
+		<div class="layout">
+			<div class="sidebar">
+				<p>Subscribe to our newsletter, get the latest posts, tips, and news in your inbox every week.</p>
+			</div>
+			<div class="post-content">
+				<h1>Rust Ownership Explained</h1>
+				<p>Ownership is the set of rules that govern how a Rust program manages memory, without a garbage collector.</p>
+				<p>Each value has an owner, there can only be one owner at a time, and the value is dropped when the owner goes out of scope.</p>
+				<p>Borrowing lets code use a value without taking ownership, through shared or mutable references.</p>
+				<div hidden>Hidden promo text that should never be part of the article.</div>
+			</div>
+			<div class="comments">
+				<p>Great article, thanks a lot, it helped me understand the borrow checker better!</p>
+			</div>
+		</div>
+		<footer><p>Copyright 2024, all rights reserved, Dev Blog Inc, made with care.</p></footer>
+	</body>
+	</html>
+	"#;
+
+	#[test]
+	fn test_main_content_extract_article() -> TestResult<()> {
+		// -- Exec
+		let content = extract_main_content(FX_HTML, MainContentOptions::default())?.ok_or("Should have content")?;
+
+		// -- Check
+		assert!(
+			content.html.contains("<h1>Rust Ownership Explained</h1>"),
+			"{}",
+			content.html
+		);
+		assert!(content.html.contains("Borrowing lets code use a value"));
+		assert!(!content.html.contains("Subscribe"), "Sidebar should be excluded");
+		assert!(!content.html.contains("Great article"), "Comments should be excluded");
+		assert!(!content.html.contains("Copyright"), "Footer should be excluded");
+		assert!(
+			!content.html.contains("Hidden promo"),
+			"Hidden content should be excluded"
+		);
+		assert!(!content.html.contains("<body>"));
+		assert_eq!(content.title.as_deref(), Some("Rust Ownership Explained | Dev Blog"));
+		assert_eq!(content.byline.as_deref(), Some("Jane Doe"));
+		assert_eq!(content.date.as_deref(), Some("2024-05-01T10:00:00Z"));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_main_content_extract_none() -> TestResult<()> {
+		// -- Exec
+		let content = extract_main_content(
+			"<nav><a href='/'>Home</a></nav><p>Short.</p>",
+			MainContentOptions::default(),
+		)?;
+
+		// -- Check
+		assert!(content.is_none());
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_main_content_extract_serde_round_trip() -> TestResult<()> {
+		// -- Setup & Fixtures
+		let content = extract_main_content(FX_HTML, MainContentOptions::default())?.ok_or("Should have content")?;
+
+		// -- Exec
+		let json = serde_json::to_string(&content)?;
+		let content_back: MainContent = serde_json::from_str(&json)?;
+
+		// -- Check
+		assert_eq!(content_back, content);
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
@@ -0,0 +1,43 @@
+use crate::{HiddenRules, SlimOptions};
+
+// region:    --- Types
+
+/// Options for the `extract_main_content` function.
+#[derive(Clone, Debug)]
+pub struct MainContentOptions {
+	/// The options used to slim the main content (default: `SlimOptions::default()` with `HiddenRules::all()`).
+	///
+	/// The `parse_mode` is ignored (the main content is always slimmed as a fragment).
+	pub slim_options: SlimOptions,
+	/// The minimum text length (in characters) of a block to be scored (default: 25).
+	pub min_text_length: usize,
+}
+
+impl Default for MainContentOptions {
+	fn default() -> Self {
+		Self {
+			slim_options: SlimOptions::default().with_hidden_rules(HiddenRules::all()),
+			min_text_length: 25,
+		}
+	}
+}
+
+// endregion: --- Types
+
+// region:    --- Constructors & Fluid API
+
+impl MainContentOptions {
+	/// Set the options used to slim the main content.
+	pub fn with_slim_options(mut self, slim_options: impl Into<SlimOptions>) -> Self {
+		self.slim_options = slim_options.into();
+		self
+	}
+
+	/// Set the minimum text length of a scored block.
+	pub fn with_min_text_length(mut self, min_text_length: usize) -> Self {
+		self.min_text_length = min_text_length;
+		self
+	}
+}
+
+// endregion: --- Constructors & Fluid API
@@ -0,0 +1,10 @@
+// region:    --- Modules
+
+mod extract_main_content;
+mod main_content_options;
+mod scoring;
+
+pub use extract_main_content::*;
+pub use main_content_options::*;
+
+// endregion: --- Modules
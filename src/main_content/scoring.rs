@@ -0,0 +1,242 @@
+use super::MainContentOptions;
+use crate::support::{BLOCK_LEVEL_TAGS, NON_CONTENT_TAGS, collapse_whitespace};
+use ego_tree::NodeId;
+use scraper::ElementRef;
+use std::collections::HashMap;
+
+// region:    --- Constants
+
+/// `class`/`id` words of content containers.
+const POSITIVE_HINTS: &[&str] = &[
+	"article", "body", "content", "entry", "main", "page", "post", "story", "text", "blog",
+];
+
+/// `class`/`id` words of boilerplate (skipped unless they also have a positive hint).
+#[rustfmt::skip]
+const NEGATIVE_HINTS: &[&str] = &[
+	"comment", "sidebar", "footer", "nav", "menu", "share", "social", "related", "promo", "banner",
+	"advert", "sponsor", "cookie", "popup", "modal", "widget", "breadcrumb", "pagination", "masthead", "disqus",
+];
+
+/// Tags never holding the main content.
+const BOILERPLATE_TAGS: &[&str] = &["nav", "aside", "footer", "form", "button", "select", "dialog"];
+
+/// ARIA roles never holding the main content.
+const BOILERPLATE_ROLES: &[&str] = &["navigation", "complementary", "banner", "contentinfo", "dialog"];
+
+/// Tags whose text is scored (along with the `div`s without block children).
+const SCORED_TAGS: &[&str] = &["p", "pre", "td", "blockquote"];
+
+// endregion: --- Constants
+
+/// The scored candidates of a document.
+pub(super) struct Scores {
+	scores: HashMap<NodeId, f64>,
+}
+
+impl Scores {
+	/// Scores the blocks under `root`: each text block adds its score to its parent (full),
+	/// grandparent (half), and great-grandparent (sixth).
+	pub(super) fn new(root: ElementRef, options: &MainContentOptions) -> Self {
+		let mut scores: HashMap<NodeId, f64> = HashMap::new();
+
+		let mut stack = vec![root];
+		while let Some(el) = stack.pop() {
+			if el.id() != root.id() && is_boilerplate(el, options) {
+				continue;
+			}
+			stack.extend(el.children().rev().filter_map(ElementRef::wrap));
+
+			if !is_scored_block(el) {
+				continue;
+			}
+			let text = collapse_whitespace(&el.text().collect::<String>());
+			let text = text.trim();
+			let len = text.chars().count();
+			if len < options.min_text_length {
+				continue;
+			}
+
+			// One point for the block, one per comma, and one per 100 characters (up to 3)
+			let score = 1.0 + text.matches(',').count() as f64 + (len / 100).min(3) as f64;
+
+			let ancestors = el
+				.ancestors()
+				.filter_map(ElementRef::wrap)
+				.take_while(|ancestor| ancestor.value().name() != "html")
+				.take(3);
+			for (level, ancestor) in ancestors.enumerate() {
+				let divider = match level {
+					0 => 1.0,
+					1 => 2.0,
+					_ => level as f64 * 3.0,
+				};
+				*scores.entry(ancestor.id()).or_insert_with(|| initial_score(ancestor)) += score / divider;
+			}
+		}
+
+		Self { scores }
+	}
+
+	/// The final score of a candidate (scaled by its link density), or `None` if the element was not scored.
+	pub(super) fn final_score(&self, el: ElementRef) -> Option<f64> {
+		self.scores.get(&el.id()).map(|score| score * (1.0 - link_density(el)))
+	}
+
+	/// The candidate with the best final score.
+	pub(super) fn top_candidate<'a>(&self, root: ElementRef<'a>) -> Option<(ElementRef<'a>, f64)> {
+		root.descendants()
+			.filter_map(ElementRef::wrap)
+			.filter_map(|el| self.final_score(el).map(|score| (el, score)))
+			.fold(None, |best: Option<(ElementRef, f64)>, (el, score)| match best {
+				Some((_, best_score)) if best_score >= score => best,
+				_ => Some((el, score)),
+			})
+	}
+}
+
+// region:    --- Support
+
+/// Whether the element (with its content) is skipped.
+pub(super) fn is_boilerplate(el: ElementRef, options: &MainContentOptions) -> bool {
+	let element = el.value();
+	let tag_name = element.name();
+	if NON_CONTENT_TAGS.contains(&tag_name) || BOILERPLATE_TAGS.contains(&tag_name) {
+		return true;
+	}
+	if options.slim_options.hidden_rules.is_hidden(element) {
+		return true;
+	}
+	if element
+		.attr("role")
+		.is_some_and(|role| BOILERPLATE_ROLES.contains(&role.trim()))
+	{
+		return true;
+	}
+	if matches!(tag_name, "body" | "article" | "main") {
+		return false;
+	}
+	let hints = class_id_hints(el);
+	has_hint(&hints, NEGATIVE_HINTS) && !has_hint(&hints, POSITIVE_HINTS)
+}
+
+/// A `div` (or `section`, `article`) without block children is scored like a paragraph.
+fn is_scored_block(el: ElementRef) -> bool {
+	let tag_name = el.value().name();
+	if SCORED_TAGS.contains(&tag_name) {
+		return true;
+	}
+	matches!(tag_name, "div" | "section" | "article")
+		&& !el
+			.children()
+			.filter_map(ElementRef::wrap)
+			.any(|child| BLOCK_LEVEL_TAGS.contains(&child.value().name()) && child.value().name() != "a")
+}
+
+/// The score of a candidate before its children blocks are added (tag and class/id hints).
+fn initial_score(el: ElementRef) -> f64 {
+	let tag_score = match el.value().name() {
+		"article" | "main" => 10.0,
+		"div" => 5.0,
+		"pre" | "td" | "blockquote" => 3.0,
+		"address" | "ol" | "ul" | "dl" | "dd" | "dt" | "li" | "form" => -3.0,
+		"h1" | "h2" | "h3" | "h4" | "h5" | "h6" | "th" => -5.0,
+		_ => 0.0,
+	};
+
+	let hints = class_id_hints(el);
+	let mut hint_score = 0.0;
+	if has_hint(&hints, POSITIVE_HINTS) {
+		hint_score += 25.0;
+	}
+	if has_hint(&hints, NEGATIVE_HINTS) {
+		hint_score -= 25.0;
+	}
+
+	tag_score + hint_score
+}
+
+/// The link text length over the text length (0 to 1).
+pub(super) fn link_density(el: ElementRef) -> f64 {
+	let text_len = text_length(el);
+	if text_len == 0 {
+		return 0.0;
+	}
+	let link_len: usize = el
+		.descendants()
+		.filter_map(ElementRef::wrap)
+		.filter(|d| d.value().name() == "a")
+		.map(text_length)
+		.sum();
+	(link_len as f64 / text_len as f64).min(1.0)
+}
+
+/// The text length, whitespace collapsed, in characters.
+pub(super) fn text_length(el: ElementRef) -> usize {
+	collapse_whitespace(&el.text().collect::<String>())
+		.trim()
+		.chars()
+		.count()
+}
+
+/// The lowercase words of the `class` and `id` of the element, split on whitespace, `-`, and `_`
+/// (e.g., `post-content main_nav` gives `post`, `content`, `main`, `nav`).
+fn class_id_hints(el: ElementRef) -> Vec<String> {
+	let element = el.value();
+	let class = element.attr("class").unwrap_or_default();
+	let id = element.attr("id").unwrap_or_default();
+	format!("{class} {id}")
+		.to_lowercase()
+		.split(|c: char| c.is_whitespace() || c == '-' || c == '_')
+		.filter(|word| !word.is_empty())
+		.map(str::to_string)
+		.collect()
+}
+
+/// Whether one of the hints is one of the words, or its plural (e.g., `comments` for `comment`).
+fn has_hint(hints: &[String], words: &[&str]) -> bool {
+	hints.iter().any(|hint| {
+		let singular = hint.strip_suffix('s').unwrap_or(hint);
+		words.contains(&hint.as_str()) || words.contains(&singular)
+	})
+}
+
+// endregion: --- Support
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	type TestResult<T> = core::result::Result<T, Box<dyn std::error::Error>>;
+
+	use super::*;
+	use scraper::{Html, Selector};
+
+	#[test]
+	fn test_main_content_scoring_is_boilerplate_hint_words() -> TestResult<()> {
+		// -- Setup & Fixtures
+		let fx_html = r#"<body>
+			<div id="pagination">1 2 3</div>
+			<div class="site-nav">Menu</div>
+			<div class="user_comments">Comments</div>
+			<div class="canvas-wrapper">Drawing</div>
+			<div class="unavailable">Unavailable</div>
+			<div class="context">Context</div>
+			<div class="post-content related">Article</div>
+			<div class="postcontent">Joined words</div>
+		</body>"#;
+		let html = Html::parse_document(fx_html);
+		let selector = Selector::parse("body > div").map_err(|err| err.to_string())?;
+		let options = MainContentOptions::default();
+
+		// -- Exec
+		let boilerplate: Vec<bool> = html.select(&selector).map(|el| is_boilerplate(el, &options)).collect();
+
+		// -- Check
+		assert_eq!(boilerplate, vec![true, true, true, false, false, false, false, false]);
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
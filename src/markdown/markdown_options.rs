@@ -0,0 +1,50 @@
+// region:    --- Types
+
+/// Options for the `to_markdown` function.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MarkdownOptions {
+	/// How links and images are written.
+	pub link_style: LinkStyle,
+	/// How headings are written.
+	pub heading_style: HeadingStyle,
+}
+
+/// Markdown link (and image) style.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LinkStyle {
+	/// `[text](https://example.com "title")`
+	#[default]
+	Inline,
+	/// `[text][1]` with the `[1]: https://example.com "title"` definitions at the end of the document.
+	Reference,
+}
+
+/// Markdown heading style.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum HeadingStyle {
+	/// `# Title`, `## Title`, ...
+	#[default]
+	Atx,
+	/// `Title` underlined with `===` (h1) or `---` (h2). Lower levels fall back to ATX.
+	Setext,
+}
+
+// endregion: --- Types
+
+// region:    --- Constructors & Fluid API
+
+impl MarkdownOptions {
+	/// Set the link style (inline or reference).
+	pub fn with_link_style(mut self, link_style: LinkStyle) -> Self {
+		self.link_style = link_style;
+		self
+	}
+
+	/// Set the heading style (ATX or Setext).
+	pub fn with_heading_style(mut self, heading_style: HeadingStyle) -> Self {
+		self.heading_style = heading_style;
+		self
+	}
+}
+
+// endregion: --- Constructors & Fluid API
@@ -0,0 +1,9 @@
+// region:    --- Modules
+
+mod markdown_options;
+mod to_markdown;
+
+pub use markdown_options::*;
+pub use to_markdown::*;
+
+// endregion: --- Modules
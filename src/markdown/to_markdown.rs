@@ -0,0 +1,971 @@
+use super::{HeadingStyle, LinkStyle, MarkdownOptions};
+use crate::{Document, Result};
+use crate::support::{NON_CONTENT_TAGS, collapse_whitespace};
+use ego_tree::NodeRef;
+use scraper::{ElementRef, node::Node};
+
+// region:    --- Constants
+
+/// Tags rendered as transparent block containers (their children become blocks).
+#[rustfmt::skip]
+const CONTAINER_TAGS: &[&str] = &[
+	"html", "body", "div", "p", "section", "article", "main", "header", "footer", "nav", "aside",
+	"address", "figure", "figcaption", "form", "fieldset", "details", "summary", "dialog", "center",
+	"hgroup", "dl", "dt", "dd", "li",
+];
+
+// endregion: --- Constants
+
+/// Converts HTML content into CommonMark/GFM Markdown.
+///
+/// Supports headings, (nested) lists, links, images, emphasis, inline code, `<pre>` code blocks
+/// (with the language hint from a `language-xxx` or `lang-xxx` class), blockquotes, horizontal rules,
+/// and GFM tables. Non-content elements (`<head>`, `<script>`, `<style>`, etc.) are skipped.
+///
+/// # Arguments
+///
+/// * `html_content` - A string slice containing the HTML content to convert.
+/// * `options` - The `MarkdownOptions` (link style, heading style).
+///
+/// # Returns
+///
+/// A `Result<String>` containing the Markdown document.
+pub fn to_markdown(html_content: &str, options: impl Into<MarkdownOptions>) -> Result<String> {
+	Ok(Document::parse(html_content).to_markdown(options))
+}
+
+/// Renders the Markdown of a parsed node (document, fragment, or element).
+pub(crate) fn markdown_from_node(node: NodeRef<Node>, options: &MarkdownOptions) -> String {
+	MdRenderer {
+		options,
+		references: Vec::new(),
+	}
+	.render(node)
+}
+
+// region:    --- Renderer
+
+struct Block {
+	md: String,
+	/// Nested lists are attached to the previous block of a list item without a blank line.
+	is_list: bool,
+}
+
+struct MdRenderer<'o> {
+	options: &'o MarkdownOptions,
+	/// The `(url, title)` of the reference-style links, in order of first use.
+	references: Vec<(String, Option<String>)>,
+}
+
+/// How the output of a node goes to its parent.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Role {
+	/// A block of a block container.
+	Block,
+	/// Inline content, appended to the parent inline content.
+	Inline,
+	/// A `<li>` of a list.
+	ListItem,
+	/// A list directly in a list (invalid but common), attached to the previous item.
+	NestedList,
+	/// A `<td>`/`<th>` of a table.
+	Cell,
+}
+
+/// What an element renders as, once its children are rendered.
+enum Kind<'a> {
+	/// Its children blocks, as is (document, fragment, `<div>`, `<p>`, etc.).
+	Container,
+	Heading(usize),
+	/// A `ul` (start `None`) or `ol` (start `Some(n)`), with the number of its next item.
+	List {
+		start: Option<u64>,
+		next: u64,
+	},
+	Blockquote,
+	Table(Vec<TableRow<'a>>),
+	/// Unknown block-ish element, its content inline.
+	OtherBlock,
+	/// A list item, with its marker (e.g., `- `, `3. `).
+	ListItem(String),
+	Cell,
+	Link,
+	/// Emphasis, with its marker (`**`, `_`, `~~`).
+	Emphasis(&'static str),
+	/// A block element inside inline content.
+	InlineBlock,
+	/// Any other inline element (its content as is).
+	Inline,
+}
+
+/// A row of a table (not of its nested tables).
+struct TableRow<'a> {
+	/// In `<thead>`, or all `<th>`.
+	is_header: bool,
+	/// The `<td>`/`<th>`, with their colspan.
+	cells: Vec<(NodeRef<'a, Node>, usize)>,
+}
+
+/// An element being rendered, until its children are.
+struct Frame<'a> {
+	node: NodeRef<'a, Node>,
+	kind: Kind<'a>,
+	role: Role,
+	blocks: Vec<Block>,
+	/// The inline content (not normalized) since the last block.
+	inline: String,
+	/// The items of a list, or the cells of a table.
+	items: Vec<String>,
+}
+
+/// The work of the renderer walk.
+enum Step<'a> {
+	Enter(NodeRef<'a, Node>, Role),
+	/// The children of the top frame are rendered.
+	Exit,
+}
+
+/// The output of a node: blocks (for the `Block` and `NestedList` roles) or Markdown (for the others).
+enum Output {
+	Blocks(Vec<Block>),
+	Md(String),
+}
+
+impl MdRenderer<'_> {
+	fn render(mut self, node: NodeRef<Node>) -> String {
+		let blocks = self.blocks(node);
+		let mut md = join_blocks(&blocks, "\n\n");
+
+		if !self.references.is_empty() {
+			md.push_str("\n\n");
+			let defs: Vec<String> = self
+				.references
+				.iter()
+				.enumerate()
+				.map(|(idx, (url, title))| format!("[{}]: {}{}", idx + 1, link_destination(url), link_title(title)))
+				.collect();
+			md.push_str(&defs.join("\n"));
+		}
+
+		md
+	}
+
+	/// Renders the node as blocks, walking its subtree with a stack (no recursion, so deep documents are safe).
+	fn blocks<'a>(&mut self, root: NodeRef<'a, Node>) -> Vec<Block> {
+		let mut blocks = Vec::new();
+		let mut frames: Vec<Frame<'a>> = Vec::new();
+		let mut steps = vec![Step::Enter(root, Role::Block)];
+
+		while let Some(step) = steps.pop() {
+			match step {
+				Step::Enter(node, role) => {
+					let Some(frame) = self.enter(node, role, &mut frames, &mut blocks) else {
+						continue;
+					};
+					steps.push(Step::Exit);
+					let children = frame_children(&frame);
+					steps.extend(children.into_iter().rev().map(|(child, role)| Step::Enter(child, role)));
+					frames.push(frame);
+				}
+				Step::Exit => {
+					let Some(frame) = frames.pop() else {
+						continue;
+					};
+					let role = frame.role;
+					let output = self.exit(frame);
+					deliver(role, output, frames.last_mut(), &mut blocks);
+				}
+			}
+		}
+
+		blocks
+	}
+
+	/// Starts a node: renders it when it has no rendered children (text, `<pre>`, `<img>`, etc.),
+	/// or returns its frame.
+	fn enter<'a>(
+		&mut self,
+		node: NodeRef<'a, Node>,
+		role: Role,
+		frames: &mut [Frame<'a>],
+		blocks: &mut Vec<Block>,
+	) -> Option<Frame<'a>> {
+		let kind = match node.value() {
+			Node::Text(text) => {
+				if let Some(parent) = frames.last_mut() {
+					parent.inline.push_str(&escape_text(&collapse_whitespace(text)));
+				}
+				return None;
+			}
+			Node::Document | Node::Fragment => Kind::Container,
+			Node::Element(_) => {
+				let el_ref = ElementRef::wrap(node)?;
+				if let Some(output) = self.leaf_output(el_ref, role) {
+					deliver(role, output, frames.last_mut(), blocks);
+					return None;
+				}
+				element_kind(el_ref, role, frames.last_mut())
+			}
+			_ => return None,
+		};
+
+		Some(Frame {
+			node,
+			kind,
+			role,
+			blocks: Vec::new(),
+			inline: String::new(),
+			items: Vec::new(),
+		})
+	}
+
+	/// The output of the elements rendered without their children.
+	fn leaf_output(&mut self, el_ref: ElementRef, role: Role) -> Option<Output> {
+		let el = el_ref.value();
+		let block = |md: String| Output::Blocks(vec![Block { md, is_list: false }]);
+
+		let output = match (role, el.name()) {
+			(Role::Block, "pre") => block(code_block(el_ref)),
+			(Role::Block, "hr") => block("---".to_string()),
+			(Role::Inline, "img") => {
+				let src = el.attr("src").map(str::trim).filter(|s| !s.is_empty());
+				let md = match src {
+					Some(src) => {
+						let alt = escape_text(&collapse_whitespace(el.attr("alt").unwrap_or_default()));
+						let title = el.attr("title").map(|t| t.to_string());
+						format!("![{}]{}", alt.trim(), self.destination(src, title))
+					}
+					None => String::new(),
+				};
+				Output::Md(md)
+			}
+			(Role::Inline, "code" | "kbd" | "samp" | "tt") => {
+				Output::Md(code_span(&collapse_whitespace(&el_ref.text().collect::<String>())))
+			}
+			(Role::Inline, "br") => Output::Md("\n".to_string()),
+			_ => return None,
+		};
+
+		Some(output)
+	}
+
+	/// Renders an element from its rendered children.
+	fn exit(&mut self, mut frame: Frame) -> Output {
+		let is_list = matches!(frame.kind, Kind::List { .. });
+		let block = match frame.kind {
+			Kind::Container => {
+				flush_inline(&mut frame.inline, &mut frame.blocks);
+				return Output::Blocks(frame.blocks);
+			}
+			Kind::Heading(level) => self.heading(&frame.inline, level),
+			Kind::List { .. } => (!frame.items.is_empty()).then(|| frame.items.join("\n")),
+			Kind::Blockquote => {
+				flush_inline(&mut frame.inline, &mut frame.blocks);
+				let inner = join_blocks(&frame.blocks, "\n\n");
+				if inner.is_empty() {
+					None
+				} else {
+					let quoted: Vec<String> = inner
+						.lines()
+						.map(|l| if l.is_empty() { ">".to_string() } else { format!("> {l}") })
+						.collect();
+					Some(quoted.join("\n"))
+				}
+			}
+			Kind::Table(rows) => table(rows, frame.items),
+			Kind::OtherBlock => {
+				let md = normalize_inline(&frame.inline);
+				(!md.is_empty()).then_some(md)
+			}
+			Kind::ListItem(marker) => {
+				flush_inline(&mut frame.inline, &mut frame.blocks);
+				return Output::Md(indent_item(&marker, &join_item_blocks(&frame.blocks)));
+			}
+			Kind::Cell => {
+				return Output::Md(single_line(&normalize_inline(&frame.inline)).replace('|', "\\|"));
+			}
+			Kind::Link => return Output::Md(self.link(frame.node, frame.inline)),
+			Kind::Emphasis(marker) => return Output::Md(wrap_inline(&frame.inline, marker)),
+			Kind::InlineBlock => return Output::Md(format!(" {} ", frame.inline)),
+			Kind::Inline => return Output::Md(frame.inline),
+		};
+
+		let blocks = block
+			.filter(|md| !md.trim().is_empty())
+			.map(|md| Block { md, is_list })
+			.into_iter()
+			.collect();
+		Output::Blocks(blocks)
+	}
+
+	fn heading(&mut self, inline: &str, level: usize) -> Option<String> {
+		let text = single_line(&normalize_inline(inline));
+		if text.is_empty() {
+			return None;
+		}
+		let md = match (self.options.heading_style, level) {
+			(HeadingStyle::Setext, 1 | 2) => {
+				let underline = if level == 1 { "=" } else { "-" };
+				let width = text.chars().count().max(3);
+				format!("{text}\n{}", underline.repeat(width))
+			}
+			_ => format!("{} {text}", "#".repeat(level)),
+		};
+		Some(md)
+	}
+
+	/// Renders a link from its content (not normalized), or the content alone without `href`.
+	fn link(&mut self, node: NodeRef<Node>, content: String) -> String {
+		let Some(el) = node.value().as_element() else {
+			return content;
+		};
+		let Some(href) = el.attr("href").map(str::trim).filter(|h| !h.is_empty()) else {
+			return content;
+		};
+		let text = single_line(&normalize_inline(&content));
+		let text = if text.is_empty() { escape_text(href) } else { text };
+		let title = el.attr("title").map(|t| t.to_string());
+		format!("[{text}]{}", self.destination(href, title))
+	}
+
+	/// Returns the link destination part, `(url "title")` or `[n]`, based on the link style.
+	fn destination(&mut self, url: &str, title: Option<String>) -> String {
+		match self.options.link_style {
+			LinkStyle::Inline => format!("({}{})", link_destination(url), link_title(&title)),
+			LinkStyle::Reference => {
+				let key = (url.to_string(), title);
+				let idx = match self.references.iter().position(|r| r == &key) {
+					Some(idx) => idx,
+					None => {
+						self.references.push(key);
+						self.references.len() - 1
+					}
+				};
+				format!("[{}]", idx + 1)
+			}
+		}
+	}
+}
+
+/// What the element renders as, in the given role.
+fn element_kind<'a>(el_ref: ElementRef<'a>, role: Role, parent: Option<&mut Frame>) -> Kind<'a> {
+	let tag = el_ref.value().name();
+	match role {
+		Role::Block | Role::NestedList => match tag {
+			"h1" | "h2" | "h3" | "h4" | "h5" | "h6" => Kind::Heading((tag.as_bytes()[1] - b'0') as usize),
+			"ul" => Kind::List { start: None, next: 1 },
+			"ol" => {
+				let start = el_ref.value().attr("start").and_then(|s| s.trim().parse().ok()).unwrap_or(1);
+				Kind::List {
+					start: Some(start),
+					next: start,
+				}
+			}
+			"blockquote" => Kind::Blockquote,
+			"table" => Kind::Table(table_rows(el_ref)),
+			_ if CONTAINER_TAGS.contains(&tag) => Kind::Container,
+			_ => Kind::OtherBlock,
+		},
+		Role::ListItem => {
+			let marker = match parent.map(|parent| &mut parent.kind) {
+				Some(Kind::List { start: Some(_), next }) => {
+					*next += 1;
+					format!("{}. ", *next - 1)
+				}
+				_ => "- ".to_string(),
+			};
+			Kind::ListItem(marker)
+		}
+		Role::Cell => Kind::Cell,
+		Role::Inline => match tag {
+			"a" => Kind::Link,
+			"strong" | "b" => Kind::Emphasis("**"),
+			"em" | "i" => Kind::Emphasis("_"),
+			"del" | "s" | "strike" => Kind::Emphasis("~~"),
+			_ if is_block_tag(tag) => Kind::InlineBlock,
+			_ => Kind::Inline,
+		},
+	}
+}
+
+/// The children to render for the frame, with their role.
+fn frame_children<'a>(frame: &Frame<'a>) -> Vec<(NodeRef<'a, Node>, Role)> {
+	let node = frame.node;
+	match &frame.kind {
+		Kind::List { .. } => node
+			.children()
+			.filter_map(|child| match child.value() {
+				Node::Element(el) if el.name() == "li" => Some((child, Role::ListItem)),
+				Node::Element(el) if matches!(el.name(), "ul" | "ol") => Some((child, Role::NestedList)),
+				_ => None,
+			})
+			.collect(),
+		Kind::Table(rows) => rows
+			.iter()
+			.flat_map(|row| row.cells.iter().map(|(cell, _)| (*cell, Role::Cell)))
+			.collect(),
+		Kind::Container | Kind::Blockquote | Kind::ListItem(_) => node
+			.children()
+			.filter_map(|child| match child.value() {
+				Node::Text(_) => Some((child, Role::Inline)),
+				Node::Element(el) if NON_CONTENT_TAGS.contains(&el.name()) => None,
+				Node::Element(el) if is_block_tag(el.name()) => Some((child, Role::Block)),
+				Node::Element(_) => Some((child, Role::Inline)),
+				Node::Document | Node::Fragment => Some((child, Role::Block)),
+				_ => None,
+			})
+			.collect(),
+		_ => node
+			.children()
+			.filter_map(|child| match child.value() {
+				Node::Text(_) => Some((child, Role::Inline)),
+				Node::Element(el) if NON_CONTENT_TAGS.contains(&el.name()) => None,
+				Node::Element(_) => Some((child, Role::Inline)),
+				_ => None,
+			})
+			.collect(),
+	}
+}
+
+/// Adds the output of a node to its parent frame (or to the root blocks).
+fn deliver(role: Role, output: Output, parent: Option<&mut Frame>, blocks: &mut Vec<Block>) {
+	let Some(parent) = parent else {
+		if let Output::Blocks(root_blocks) = output {
+			blocks.extend(root_blocks);
+		}
+		return;
+	};
+	match output {
+		Output::Blocks(child_blocks) if role == Role::NestedList => {
+			let nested = join_blocks(&child_blocks, "\n");
+			if nested.is_empty() {
+				return;
+			}
+			match parent.items.last_mut() {
+				Some(last) => {
+					let indent = last.find(' ').map(|i| i + 1).unwrap_or(2);
+					last.push('\n');
+					last.push_str(&indent_lines(&nested, indent));
+				}
+				None => parent.items.push(nested),
+			}
+		}
+		Output::Blocks(child_blocks) => {
+			flush_inline(&mut parent.inline, &mut parent.blocks);
+			parent.blocks.extend(child_blocks);
+		}
+		Output::Md(md) if role == Role::Inline => parent.inline.push_str(&md),
+		Output::Md(md) => parent.items.push(md),
+	}
+}
+
+/// The rows of a table (direct, or in thead/tbody/tfoot), without the rows without cells.
+fn table_rows(table: ElementRef) -> Vec<TableRow> {
+	let mut row_nodes: Vec<(bool, NodeRef<Node>)> = Vec::new();
+	for child in table.children() {
+		let Node::Element(el) = child.value() else {
+			continue;
+		};
+		match el.name() {
+			"tr" => row_nodes.push((false, child)),
+			"thead" | "tbody" | "tfoot" => {
+				let in_thead = el.name() == "thead";
+				for tr in child.children() {
+					if matches!(tr.value(), Node::Element(el) if el.name() == "tr") {
+						row_nodes.push((in_thead, tr));
+					}
+				}
+			}
+			_ => {}
+		}
+	}
+
+	let mut rows = Vec::new();
+	for (in_thead, tr) in row_nodes {
+		let mut cells = Vec::new();
+		let mut all_th = true;
+		for cell in tr.children() {
+			let Some(cell_ref) = ElementRef::wrap(cell) else {
+				continue;
+			};
+			let cell_tag = cell_ref.value().name();
+			if cell_tag != "td" && cell_tag != "th" {
+				continue;
+			}
+			all_th &= cell_tag == "th";
+			let colspan: usize = cell_ref.value().attr("colspan").and_then(|c| c.trim().parse().ok()).unwrap_or(1);
+			cells.push((cell, colspan.clamp(1, 1000)));
+		}
+		if !cells.is_empty() {
+			rows.push(TableRow {
+				is_header: in_thead || all_th,
+				cells,
+			});
+		}
+	}
+	rows
+}
+
+/// Formats the GFM table from its rows and the rendered cells (in order).
+fn table(rows: Vec<TableRow>, cells: Vec<String>) -> Option<String> {
+	let mut cells = cells.into_iter();
+	let mut rows: Vec<(bool, Vec<String>)> = rows
+		.into_iter()
+		.map(|row| {
+			let mut mds = Vec::new();
+			for (_, colspan) in row.cells {
+				mds.push(cells.next().unwrap_or_default());
+				for _ in 1..colspan {
+					mds.push(String::new());
+				}
+			}
+			(row.is_header, mds)
+		})
+		.collect();
+
+	if rows.is_empty() {
+		return None;
+	}
+
+	// -- Format (GFM requires a header row: the first `<thead>` or all `<th>` row, or else the first row)
+	let col_count = rows.iter().map(|(_, cells)| cells.len()).max().unwrap_or(0);
+	let header_idx = rows.iter().position(|(is_header, _)| *is_header).unwrap_or(0);
+	let (_, header) = rows.remove(header_idx);
+	let format_row = |cells: &[String]| {
+		let mut line = String::from("|");
+		for idx in 0..col_count {
+			let cell = cells.get(idx).map(|s| s.as_str()).unwrap_or("");
+			line.push(' ');
+			line.push_str(cell);
+			line.push_str(" |");
+		}
+		line
+	};
+
+	let mut lines = vec![format_row(&header), format!("|{}", " --- |".repeat(col_count))];
+	for (_, cells) in rows {
+		lines.push(format_row(&cells));
+	}
+
+	Some(lines.join("\n"))
+}
+
+// endregion: --- Renderer
+
+// region:    --- Support
+
+fn is_block_tag(tag: &str) -> bool {
+	CONTAINER_TAGS.contains(&tag)
+		|| matches!(
+			tag,
+			"h1" | "h2" | "h3" | "h4" | "h5" | "h6" | "ul" | "ol" | "pre" | "blockquote" | "table" | "hr"
+		)
+}
+
+fn flush_inline(inline: &mut String, blocks: &mut Vec<Block>) {
+	let md = normalize_inline(inline);
+	if !md.is_empty() {
+		blocks.push(Block { md, is_list: false });
+	}
+	inline.clear();
+}
+
+fn join_blocks(blocks: &[Block], sep: &str) -> String {
+	blocks.iter().map(|b| b.md.as_str()).collect::<Vec<_>>().join(sep)
+}
+
+/// Joins the blocks of a list item, keeping nested lists tight.
+fn join_item_blocks(blocks: &[Block]) -> String {
+	let mut md = String::new();
+	for (idx, block) in blocks.iter().enumerate() {
+		if idx > 0 {
+			md.push_str(if block.is_list { "\n" } else { "\n\n" });
+		}
+		md.push_str(&block.md);
+	}
+	md
+}
+
+/// Prefixes the first line with the marker, and indents the other lines to the marker width.
+fn indent_item(marker: &str, content: &str) -> String {
+	if content.is_empty() {
+		return marker.trim_end().to_string();
+	}
+	let mut lines = content.lines();
+	let mut md = format!("{marker}{}", lines.next().unwrap_or_default());
+	let rest: Vec<&str> = lines.collect();
+	if !rest.is_empty() {
+		md.push('\n');
+		md.push_str(&indent_lines(&rest.join("\n"), marker.len()));
+	}
+	md
+}
+
+fn indent_lines(content: &str, width: usize) -> String {
+	let indent = " ".repeat(width);
+	content
+		.lines()
+		.map(|l| if l.is_empty() { String::new() } else { format!("{indent}{l}") })
+		.collect::<Vec<_>>()
+		.join("\n")
+}
+
+/// Normalizes collapsed inline content: trims each line (lines only come from `<br>`),
+/// drops the empty ones, escapes their block markers, and joins them with a hard line break.
+fn normalize_inline(md: &str) -> String {
+	md.split('\n')
+		.map(|line| collapse_whitespace(line).trim().to_string())
+		.filter(|line| !line.is_empty())
+		.map(|line| escape_line_start(&line))
+		.collect::<Vec<_>>()
+		.join("\\\n")
+}
+
+/// Escapes the block marker starting a line (e.g., `# a`, `> a`, `- a`, `1. a`), so the text does not become
+/// a heading, a blockquote, a list, or a setext underline.
+fn escape_line_start(line: &str) -> String {
+	if line.starts_with(['#', '>', '-', '+', '=']) {
+		return format!("\\{line}");
+	}
+	let digits = line.len() - line.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+	let rest = &line[digits..];
+	let is_list_marker =
+		rest.starts_with(['.', ')']) && (rest.len() == 1 || rest[1..].starts_with(char::is_whitespace));
+	if digits > 0 && is_list_marker {
+		format!("{}\\{rest}", &line[..digits])
+	} else {
+		line.to_string()
+	}
+}
+
+/// Flattens a (normalized) inline content on one line, for headings, link text, and table cells.
+fn single_line(md: &str) -> String {
+	md.replace("\\\n", " ")
+}
+
+/// Escapes the Markdown special characters of a text (and the `<` of HTML tags, the `&` of entities).
+fn escape_text(text: &str) -> String {
+	let mut out = String::with_capacity(text.len());
+	let chars: Vec<char> = text.chars().collect();
+	for (idx, &c) in chars.iter().enumerate() {
+		match c {
+			'\\' | '*' | '`' | '[' | ']' | '<' => {
+				out.push('\\');
+				out.push(c);
+			}
+			'&' => {
+				if is_entity_like(&chars[idx + 1..]) {
+					out.push('\\');
+				}
+				out.push(c);
+			}
+			// Intraword underscores (e.g., snake_case) cannot start emphasis.
+			'_' => {
+				let prev_alnum = idx > 0 && chars[idx - 1].is_alphanumeric();
+				let next_alnum = chars.get(idx + 1).is_some_and(|n| n.is_alphanumeric());
+				if !(prev_alnum && next_alnum) {
+					out.push('\\');
+				}
+				out.push(c);
+			}
+			_ => out.push(c),
+		}
+	}
+	out
+}
+
+/// Whether the characters after a `&` read as an entity (e.g., `amp;`, `#39;`, `#x27;`).
+fn is_entity_like(chars: &[char]) -> bool {
+	let Some(end) = chars.iter().position(|c| *c == ';') else {
+		return false;
+	};
+	let name = &chars[..end];
+	match name {
+		['#', 'x' | 'X', hex @ ..] => !hex.is_empty() && hex.iter().all(char::is_ascii_hexdigit),
+		['#', digits @ ..] => !digits.is_empty() && digits.iter().all(char::is_ascii_digit),
+		[first, ..] => first.is_ascii_alphabetic() && name.iter().all(char::is_ascii_alphanumeric),
+		[] => false,
+	}
+}
+
+/// Wraps inline content with an emphasis marker, keeping the surrounding whitespace outside.
+fn wrap_inline(content: &str, marker: &str) -> String {
+	let trimmed = content.trim();
+	if trimmed.is_empty() {
+		return content.to_string();
+	}
+	let lead = if content.starts_with(char::is_whitespace) { " " } else { "" };
+	let trail = if content.ends_with(char::is_whitespace) { " " } else { "" };
+	format!("{lead}{marker}{trimmed}{marker}{trail}")
+}
+
+fn code_span(code: &str) -> String {
+	let code = code.trim();
+	if code.is_empty() {
+		return String::new();
+	}
+	let fence = "`".repeat(max_backtick_run(code) + 1);
+	if code.starts_with('`') || code.ends_with('`') {
+		format!("{fence} {code} {fence}")
+	} else {
+		format!("{fence}{code}{fence}")
+	}
+}
+
+fn code_block(pre: ElementRef) -> String {
+	// The language hint can be on the <pre> or on its <code> child.
+	let code_child = pre.children().filter_map(ElementRef::wrap).find(|c| c.value().name() == "code");
+	let lang = std::iter::once(pre)
+		.chain(code_child)
+		.flat_map(|el| el.value().classes().map(|c| c.to_string()).collect::<Vec<_>>())
+		.find_map(|class| {
+			class
+				.strip_prefix("language-")
+				.or_else(|| class.strip_prefix("lang-"))
+				.map(|l| l.to_string())
+		})
+		.unwrap_or_default();
+
+	let code = pre.text().collect::<String>();
+	let code = code.trim_end_matches(['\n', '\r']);
+	let fence = "`".repeat(max_backtick_run(code).max(2) + 1);
+
+	format!("{fence}{lang}\n{code}\n{fence}")
+}
+
+fn max_backtick_run(text: &str) -> usize {
+	let mut max = 0;
+	let mut run = 0;
+	for c in text.chars() {
+		if c == '`' {
+			run += 1;
+			max = max.max(run);
+		} else {
+			run = 0;
+		}
+	}
+	max
+}
+
+fn link_destination(url: &str) -> String {
+	if url.contains([' ', '(', ')']) {
+		format!("<{}>", url.replace('<', "%3C").replace('>', "%3E"))
+	} else {
+		url.to_string()
+	}
+}
+
+fn link_title(title: &Option<String>) -> String {
+	match title.as_deref().map(str::trim) {
+		Some(title) if !title.is_empty() => format!(" \"{}\"", title.replace('"', "\\\"")),
+		_ => String::new(),
+	}
+}
+
+// endregion: --- Support
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	type TestResult<T> = core::result::Result<T, Box<dyn std::error::Error>>;
+
+	use super::*;
+
+	#[test]
+	fn test_markdown_to_markdown_blocks_and_inline() -> TestResult<()> {
+		// -- Setup & Fixtures
+		let fx_html = r#"
+		<html>
+		<head><title>Ignored</title><style>p { color: red }</style></head>
+		<body>
+			<h1>Main   Title</h1>
+			<p>Some <b>bold</b>, <em>emphasis </em>and <code>code_span</code>.<br>Next line with snake_case.</p>
+			<p><a href="https://example.com" title="Ex">Example</a> <img src="/a.png" alt="Alt text"></p>
+			<blockquote><p>Quoted</p><p>Second</p></blockquote>
+			<hr>
+		</body>
+		</html>
+		"#;
+
+		// -- Exec
+		let md = to_markdown(fx_html, MarkdownOptions::default())?;
+
+		// -- Check
+		let expected = r#"# Main Title
+
+Some **bold**, _emphasis_ and `code_span`.\
+Next line with snake_case.
+
+[Example](https://example.com "Ex") ![Alt text](/a.png)
+
+> Quoted
+>
+> Second
+
+---"#;
+		assert_eq!(md, expected);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_markdown_to_markdown_nested_lists() -> TestResult<()> {
+		// -- Setup & Fixtures
+		let fx_html = r#"
+		<ul>
+			<li>One</li>
+			<li>Two
+				<ol start="3">
+					<li>Three</li>
+					<li><p>Four</p><p>More</p></li>
+				</ol>
+			</li>
+		</ul>
+		"#;
+
+		// -- Exec
+		let md = to_markdown(fx_html, MarkdownOptions::default())?;
+
+		// -- Check
+		let expected = "- One\n- Two\n  3. Three\n  4. Four\n\n     More";
+		assert_eq!(md, expected);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_markdown_to_markdown_code_block_with_lang() -> TestResult<()> {
+		// -- Setup & Fixtures
+		let fx_html = "<pre><code class=\"language-rust\">fn main() {\n    println!(\"*hi*\");\n}\n</code></pre>";
+
+		// -- Exec
+		let md = to_markdown(fx_html, MarkdownOptions::default())?;
+
+		// -- Check
+		assert_eq!(md, "```rust\nfn main() {\n    println!(\"*hi*\");\n}\n```");
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_markdown_to_markdown_gfm_table() -> TestResult<()> {
+		// -- Setup & Fixtures
+		let fx_html = r#"
+		<table>
+			<thead><tr><th>Name</th><th>Value</th></tr></thead>
+			<tbody>
+				<tr><td>a|b</td><td><b>1</b></td></tr>
+				<tr><td colspan="2">wide</td></tr>
+			</tbody>
+		</table>
+		"#;
+
+		// -- Exec
+		let md = to_markdown(fx_html, MarkdownOptions::default())?;
+
+		// -- Check
+		let expected = "| Name | Value |\n| --- | --- |\n| a\\|b | **1** |\n| wide |  |";
+		assert_eq!(md, expected);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_markdown_to_markdown_table_header_row() -> TestResult<()> {
+		// -- Setup & Fixtures
+		let fx_th_later =
+			"<table><tr><td>caption</td></tr><tr><th>Name</th><th>Value</th></tr><tr><td>a</td><td>1</td></tr></table>";
+		let fx_no_header = "<table><tr><td>a</td><td>1</td></tr><tr><td>b</td><td>2</td></tr></table>";
+
+		// -- Exec
+		let th_later = to_markdown(fx_th_later, MarkdownOptions::default())?;
+		let no_header = to_markdown(fx_no_header, MarkdownOptions::default())?;
+
+		// -- Check
+		assert_eq!(th_later, "| Name | Value |\n| --- | --- |\n| caption |  |\n| a | 1 |");
+		assert_eq!(no_header, "| a | 1 |\n| --- | --- |\n| b | 2 |");
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_markdown_to_markdown_deep_nesting() -> TestResult<()> {
+		// -- Setup & Fixtures
+		let fx_html = format!(
+			"{}<p>Deep <b>{}bold{}</b></p>{}",
+			"<div>".repeat(2000),
+			"<span>".repeat(2000),
+			"</span>".repeat(2000),
+			"</div>".repeat(2000)
+		);
+
+		// -- Exec
+		let md = to_markdown(&fx_html, MarkdownOptions::default())?;
+
+		// -- Check
+		assert_eq!(md, "Deep **bold**");
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_markdown_to_markdown_reference_links_and_setext() -> TestResult<()> {
+		// -- Setup & Fixtures
+		let fx_html = r#"
+		<h1>Title</h1>
+		<h2>Sub</h2>
+		<h3>Third</h3>
+		<p><a href="/a">A</a>, <a href="/b">B</a> and <a href="/a">A again</a></p>
+		"#;
+		let options = MarkdownOptions::default()
+			.with_link_style(LinkStyle::Reference)
+			.with_heading_style(HeadingStyle::Setext);
+
+		// -- Exec
+		let md = to_markdown(fx_html, options)?;
+
+		// -- Check
+		let expected = "Title\n=====\n\nSub\n---\n\n### Third\n\n[A][1], [B][2] and [A again][1]\n\n[1]: /a\n[2]: /b";
+		assert_eq!(md, expected);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_markdown_to_markdown_escape_html_and_entities() -> TestResult<()> {
+		// -- Setup & Fixtures
+		let fx_html =
+			"<p>&lt;script&gt;alert(1)&lt;/script&gt;</p><p>&amp;amp; &amp;#39; &amp;#x27; and R&amp;D &amp; co</p>";
+
+		// -- Exec
+		let md = to_markdown(fx_html, MarkdownOptions::default())?;
+
+		// -- Check
+		assert_eq!(
+			md,
+			"\\<script>alert(1)\\</script>\n\n\\&amp; \\&#39; \\&#x27; and R&D & co"
+		);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_markdown_to_markdown_escape_block_markers() -> TestResult<()> {
+		// -- Setup & Fixtures
+		let fx_html = r#"<p># not heading</p><p>1. not list</p><p>2) nor this</p><p>- nor this</p>
+		<p>+ plus</p><p>&gt; not quote</p><p>Text<br>===<br>--- </p><p>3.14 and -5 in 1986.</p>"#;
+
+		// -- Exec
+		let md = to_markdown(fx_html, MarkdownOptions::default())?;
+
+		// -- Check
+		let expected = "\\# not heading\n\n1\\. not list\n\n2\\) nor this\n\n\\- nor this\n\n\\+ plus\n\n\\> not quote\n\n\
+Text\\\n\\===\\\n\\---\n\n3.14 and -5 in 1986.";
+		assert_eq!(md, expected);
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
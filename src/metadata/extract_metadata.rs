@@ -0,0 +1,300 @@
+use super::PageMetadata;
+use crate::support::collapse_whitespace;
+use crate::{Document, Result};
+use scraper::{ElementRef, Html};
+use serde_json::Value;
+
+/// Extracts the page metadata (title, description, canonical URL, OpenGraph, Twitter card, JSON-LD, etc.).
+///
+/// Run it before `slim`, which only keeps a few `<meta>` tags. When a property is present several times,
+/// the first non-empty value wins.
+pub fn extract_metadata(html_content: &str) -> Result<PageMetadata> {
+	Ok(Document::parse(html_content).metadata())
+}
+
+/// Extracts the metadata of an already parsed HTML (see `Document::metadata`).
+pub(crate) fn metadata_from_html(html: &Html) -> PageMetadata {
+	let mut metadata = PageMetadata::default();
+	let mut apple_touch_icon: Option<String> = None;
+
+	for el in html.root_element().descendants().filter_map(ElementRef::wrap) {
+		let element = el.value();
+		match element.name() {
+			"html" => set_if_none(&mut metadata.lang, element.attr("lang")),
+
+			"title" if !is_in_svg(el) => {
+				let title = collapse_whitespace(&el.text().collect::<String>());
+				set_if_none(&mut metadata.title, Some(&title));
+			}
+
+			"meta" => {
+				let Some(key) = element
+					.attr("property")
+					.or(element.attr("name"))
+					.or(element.attr("itemprop"))
+				else {
+					continue;
+				};
+				let content = element.attr("content");
+				let slot = match key.trim().to_ascii_lowercase().as_str() {
+					"description" => &mut metadata.description,
+					"author" | "article:author" => &mut metadata.author,
+					"article:published_time" | "datepublished" => &mut metadata.published,
+					"article:modified_time" | "og:updated_time" | "datemodified" => &mut metadata.modified,
+					"og:title" => &mut metadata.open_graph.title,
+					"og:description" => &mut metadata.open_graph.description,
+					"og:url" => &mut metadata.open_graph.url,
+					"og:image" | "og:image:url" => &mut metadata.open_graph.image,
+					"og:image:alt" => &mut metadata.open_graph.image_alt,
+					"og:site_name" => &mut metadata.open_graph.site_name,
+					"og:type" => &mut metadata.open_graph.kind,
+					"og:locale" => &mut metadata.open_graph.locale,
+					"twitter:card" => &mut metadata.twitter.card,
+					"twitter:site" => &mut metadata.twitter.site,
+					"twitter:creator" => &mut metadata.twitter.creator,
+					"twitter:title" => &mut metadata.twitter.title,
+					"twitter:description" => &mut metadata.twitter.description,
+					"twitter:image" | "twitter:image:src" => &mut metadata.twitter.image,
+					_ => continue,
+				};
+				set_if_none(slot, content);
+			}
+
+			"link" => {
+				let rel = element.attr("rel").unwrap_or_default().to_ascii_lowercase();
+				let href = element.attr("href");
+				let mut rels = rel.split_ascii_whitespace();
+				if rels.clone().any(|r| r == "canonical") {
+					set_if_none(&mut metadata.canonical_url, href);
+				} else if rels.clone().any(|r| r == "icon") {
+					set_if_none(&mut metadata.favicon, href);
+				} else if rels.any(|r| r == "apple-touch-icon") {
+					set_if_none(&mut apple_touch_icon, href);
+				}
+			}
+
+			"script"
+				if element
+					.attr("type")
+					.is_some_and(|t| t.trim().eq_ignore_ascii_case("application/ld+json")) =>
+			{
+				let json = el.text().collect::<String>();
+				if let Ok(value) = serde_json::from_str::<Value>(&json) {
+					metadata.json_ld.push(value);
+				}
+			}
+
+			// e.g., `<time itemprop="datePublished" datetime="...">`
+			_ => match element
+				.attr("itemprop")
+				.map(|p| p.trim().to_ascii_lowercase())
+				.as_deref()
+			{
+				Some("datepublished") => set_if_none(&mut metadata.published, element.attr("datetime")),
+				Some("datemodified") => set_if_none(&mut metadata.modified, element.attr("datetime")),
+				_ => (),
+			},
+		}
+	}
+
+	if metadata.favicon.is_none() {
+		metadata.favicon = apple_touch_icon;
+	}
+	if metadata.published.is_none() {
+		metadata.published = find_json_ld_string(&metadata.json_ld, "datePublished");
+	}
+	if metadata.modified.is_none() {
+		metadata.modified = find_json_ld_string(&metadata.json_ld, "dateModified");
+	}
+
+	metadata
+}
+
+// region:    --- Support
+
+/// Sets the trimmed value when the slot is empty and the value is not.
+fn set_if_none(slot: &mut Option<String>, value: Option<&str>) {
+	if slot.is_some() {
+		return;
+	}
+	if let Some(value) = value.map(str::trim).filter(|v| !v.is_empty()) {
+		*slot = Some(value.to_string());
+	}
+}
+
+/// Whether the element is inside an `<svg>` (e.g., an svg `<title>`).
+fn is_in_svg(el: ElementRef) -> bool {
+	el.ancestors()
+		.filter_map(ElementRef::wrap)
+		.any(|ancestor| ancestor.value().name() == "svg")
+}
+
+/// Finds the first string property with the given name in the JSON-LD blocks (including arrays and `@graph`).
+fn find_json_ld_string(json_ld: &[Value], name: &str) -> Option<String> {
+	let mut stack: Vec<&Value> = json_ld.iter().rev().collect();
+	while let Some(value) = stack.pop() {
+		match value {
+			Value::Object(obj) => {
+				if let Some(Value::String(s)) = obj.get(name) {
+					return Some(s.clone());
+				}
+				if let Some(graph) = obj.get("@graph") {
+					stack.push(graph);
+				}
+			}
+			Value::Array(items) => stack.extend(items.iter().rev()),
+			_ => (),
+		}
+	}
+	None
+}
+
+// endregion: --- Support
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	type TestResult<T> = core::result::Result<T, Box<dyn std::error::Error>>;
+
+	use super::*;
+	use serde_json::json;
+
+	#[test]
+	fn test_metadata_extract_metadata() -> TestResult<()> {
+		// -- Setup & Fixtures
+		let fx_html = r#"
+		<html lang="en-US">
+		<head>
+			<title> My  Article | Site </title>
+			<meta name="description" content="An article about things.">
+			<meta name="author" content="Jane Doe">
+			<meta property="og:title" content="My Article">
+			<meta property="og:type" content="article">
+			<meta property="og:image" content="https://example.com/cover.png">
+			<meta property="article:published_time" content="2024-05-01">
+			<meta name="twitter:card" content="summary_large_image">
+			<meta name="twitter:site" content="@site">
+			<link rel="canonical" href="https://example.com/my-article">
+			<link rel="apple-touch-icon" href="/apple.png">
+			<link rel="shortcut icon" href="/favicon.ico">
+			<script type="application/ld+json">
+				{ "@context": "https://schema.org", "@graph": [{ "@type": "Article", "dateModified": "2024-06-01" }] }
+			</script>
+			<script type="application/ld+json">{ invalid </script>
+		</head>
+		<body><svg><title>Icon</title></svg><p>Content</p></body>
+		</html>
+		"#;
+
+		// -- Exec
+		let metadata = extract_metadata(fx_html)?;
+
+		// -- Check
+		assert_eq!(metadata.title.as_deref(), Some("My Article | Site"));
+		assert_eq!(metadata.description.as_deref(), Some("An article about things."));
+		assert_eq!(
+			metadata.canonical_url.as_deref(),
+			Some("https://example.com/my-article")
+		);
+		assert_eq!(metadata.lang.as_deref(), Some("en-US"));
+		assert_eq!(metadata.favicon.as_deref(), Some("/favicon.ico"));
+		assert_eq!(metadata.author.as_deref(), Some("Jane Doe"));
+		assert_eq!(metadata.published.as_deref(), Some("2024-05-01"));
+		assert_eq!(metadata.modified.as_deref(), Some("2024-06-01"));
+		assert_eq!(metadata.open_graph.title.as_deref(), Some("My Article"));
+		assert_eq!(metadata.open_graph.kind.as_deref(), Some("article"));
+		assert_eq!(metadata.twitter.card.as_deref(), Some("summary_large_image"));
+		assert_eq!(metadata.twitter.site.as_deref(), Some("@site"));
+		assert_eq!(metadata.json_ld.len(), 1);
+		assert_eq!(serde_json::to_value(&metadata.open_graph)?["type"], json!("article"));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_metadata_extract_metadata_title_precedence() -> TestResult<()> {
+		// -- Setup & Fixtures
+		let fx_html = r#"
+		<html><head>
+			<meta property="og:title" content="  ">
+			<meta property="og:title" content="OG Title">
+			<meta property="og:title" content="Second OG Title">
+			<meta name="twitter:title" content="Twitter Title">
+			<title>Page Title</title>
+			<title>Second Page Title</title>
+		</head><body></body></html>
+		"#;
+
+		// -- Exec
+		let metadata = extract_metadata(fx_html)?;
+
+		// -- Check
+		// Each source has its own field (no fallback between them), and the first non-empty value wins
+		assert_eq!(metadata.title.as_deref(), Some("Page Title"));
+		assert_eq!(metadata.open_graph.title.as_deref(), Some("OG Title"));
+		assert_eq!(metadata.twitter.title.as_deref(), Some("Twitter Title"));
+
+		// No `<title>`: the OpenGraph and Twitter titles are not used as the page title
+		let metadata = extract_metadata(r#"<head><meta property="og:title" content="OG Title"></head>"#)?;
+		assert_eq!(metadata.title, None);
+		assert_eq!(metadata.open_graph.title.as_deref(), Some("OG Title"));
+		assert_eq!(metadata.twitter.title, None);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_metadata_extract_metadata_name_and_property_keys() -> TestResult<()> {
+		// -- Setup & Fixtures
+		let fx_html = r#"
+		<html><head>
+			<meta property="description" content="From property">
+			<meta name="description" content="From name">
+			<meta name="og:image" content="https://example.com/name.png">
+			<meta property="twitter:card" content="summary">
+			<meta property="og:site_name" name="og:locale" content="Site">
+			<meta name="AUTHOR" content=" Jane ">
+		</head><body></body></html>
+		"#;
+
+		// -- Exec
+		let metadata = extract_metadata(fx_html)?;
+
+		// -- Check
+		assert_eq!(metadata.description.as_deref(), Some("From property"));
+		assert_eq!(
+			metadata.open_graph.image.as_deref(),
+			Some("https://example.com/name.png")
+		);
+		assert_eq!(metadata.twitter.card.as_deref(), Some("summary"));
+		// `property` is the key when both are present
+		assert_eq!(metadata.open_graph.site_name.as_deref(), Some("Site"));
+		assert_eq!(metadata.open_graph.locale, None);
+		assert_eq!(metadata.author.as_deref(), Some("Jane"));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_metadata_extract_metadata_svg_title_ignored() -> TestResult<()> {
+		// -- Setup & Fixtures
+		let fx_html = r#"
+		<html><head></head>
+		<body>
+			<svg><title>Icon</title><g><title>Nested icon</title></g></svg>
+			<p>Content</p>
+		</body></html>
+		"#;
+
+		// -- Exec
+		let metadata = extract_metadata(fx_html)?;
+
+		// -- Check
+		assert_eq!(metadata.title, None);
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
@@ -0,0 +1,9 @@
+// region:    --- Modules
+
+mod extract_metadata;
+mod page_metadata;
+
+pub use extract_metadata::*;
+pub use page_metadata::*;
+
+// endregion: --- Modules
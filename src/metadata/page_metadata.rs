@@ -0,0 +1,65 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+// region:    --- Types
+
+/// The metadata of a page, mostly from its `<head>` (see `extract_metadata`).
+///
+/// The values are as found in the page (e.g., URLs are not resolved, dates are not parsed).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PageMetadata {
+	/// The `<title>` text.
+	pub title: Option<String>,
+	/// The `<meta name="description">` content.
+	pub description: Option<String>,
+	/// The `<link rel="canonical">` href.
+	pub canonical_url: Option<String>,
+	/// The `<html lang>` attribute.
+	pub lang: Option<String>,
+	/// The `og:*` meta properties.
+	pub open_graph: OpenGraph,
+	/// The `twitter:*` meta properties.
+	pub twitter: TwitterCard,
+	/// The `<link rel="icon">` (or `shortcut icon`, `apple-touch-icon`) href.
+	pub favicon: Option<String>,
+	/// The `<meta name="author">` (or `article:author`) content.
+	pub author: Option<String>,
+	/// The publication date (`article:published_time`, `itemprop="datePublished"`, or JSON-LD `datePublished`).
+	pub published: Option<String>,
+	/// The modification date (`article:modified_time`, `og:updated_time`, `itemprop="dateModified"`,
+	/// or JSON-LD `dateModified`).
+	pub modified: Option<String>,
+	/// The `<script type="application/ld+json">` blocks (the invalid JSON ones are skipped).
+	pub json_ld: Vec<Value>,
+}
+
+/// The OpenGraph (`og:*`) properties of a page.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct OpenGraph {
+	pub title: Option<String>,
+	pub description: Option<String>,
+	pub url: Option<String>,
+	pub image: Option<String>,
+	pub image_alt: Option<String>,
+	pub site_name: Option<String>,
+	/// The `og:type` (e.g., `article`, `website`).
+	#[serde(rename = "type")]
+	pub kind: Option<String>,
+	pub locale: Option<String>,
+}
+
+/// The Twitter card (`twitter:*`) properties of a page.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TwitterCard {
+	/// The card type (e.g., `summary_large_image`).
+	pub card: Option<String>,
+	/// The `@username` of the website.
+	pub site: Option<String>,
+	/// The `@username` of the content creator.
+	pub creator: Option<String>,
+	pub title: Option<String>,
+	pub description: Option<String>,
+	pub image: Option<String>,
+}
+
+// endregion: --- Types
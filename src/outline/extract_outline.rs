@@ -0,0 +1,200 @@
+use super::OutlineHeading;
+use crate::support::{NON_CONTENT_TAGS, collapse_whitespace};
+use crate::{Document, Result};
+use scraper::{ElementRef, Html};
+use std::collections::HashSet;
+
+/// Extracts the heading tree (`<h1>` to `<h6>`) of a page, to build tables of contents and navigation.
+///
+/// Each heading holds the following headings of a lower level (a skipped level, e.g., `h2` then `h4`, still nests).
+/// Headings without text, or inside non-content elements (e.g., `<template>`, `<noscript>`), are skipped.
+/// Headings without `id` get a slug anchor, made unique against the document ids and the other slugs.
+pub fn outline(html_content: &str) -> Result<Vec<OutlineHeading>> {
+	Ok(Document::parse(html_content).outline())
+}
+
+/// Extracts the outline of an already parsed HTML (see `Document::outline`).
+pub(crate) fn outline_from_html(html: &Html) -> Vec<OutlineHeading> {
+	let root = html.root_element();
+
+	// The generated anchors must not collide with the existing ids
+	let mut anchors: HashSet<String> = root
+		.descendants()
+		.filter_map(ElementRef::wrap)
+		.filter_map(|el| el.value().id())
+		.map(str::to_string)
+		.collect();
+
+	// The open headings, from the top level
+	let mut stack: Vec<OutlineHeading> = Vec::new();
+	let mut roots: Vec<OutlineHeading> = Vec::new();
+
+	for el in root.descendants().filter_map(ElementRef::wrap) {
+		let Some(level) = heading_level(el.value().name()) else {
+			continue;
+		};
+		let text = collapse_whitespace(&el.text().collect::<String>()).trim().to_string();
+		if text.is_empty() || is_in_non_content(el) {
+			continue;
+		}
+
+		let id = heading_id(el);
+		let anchor = match &id {
+			Some(id) => id.clone(),
+			None => unique_slug(&text, &mut anchors),
+		};
+
+		while stack.last().is_some_and(|open| open.level >= level) {
+			close_last(&mut stack, &mut roots);
+		}
+		stack.push(OutlineHeading {
+			level,
+			text,
+			id,
+			anchor,
+			children: Vec::new(),
+		});
+	}
+	while !stack.is_empty() {
+		close_last(&mut stack, &mut roots);
+	}
+
+	roots
+}
+
+// region:    --- Support
+
+/// Moves the last open heading to its parent (or to the roots).
+fn close_last(stack: &mut Vec<OutlineHeading>, roots: &mut Vec<OutlineHeading>) {
+	if let Some(heading) = stack.pop() {
+		match stack.last_mut() {
+			Some(parent) => parent.children.push(heading),
+			None => roots.push(heading),
+		}
+	}
+}
+
+/// The level of a `<h1>` to `<h6>` tag.
+fn heading_level(tag: &str) -> Option<u8> {
+	match tag.as_bytes() {
+		[b'h', level @ b'1'..=b'6'] => Some(level - b'0'),
+		_ => None,
+	}
+}
+
+/// The non-empty `id` of the heading, or of its first descendant with one.
+fn heading_id(el: ElementRef) -> Option<String> {
+	el.descendants()
+		.filter_map(ElementRef::wrap)
+		.filter_map(|el| el.value().id())
+		.map(str::trim)
+		.find(|id| !id.is_empty())
+		.map(str::to_string)
+}
+
+fn is_in_non_content(el: ElementRef) -> bool {
+	el.ancestors()
+		.filter_map(ElementRef::wrap)
+		.any(|ancestor| NON_CONTENT_TAGS.contains(&ancestor.value().name()))
+}
+
+/// A slug of the text (GitHub style), suffixed with `-1`, `-2`, etc., when already taken.
+fn unique_slug(text: &str, taken: &mut HashSet<String>) -> String {
+	let slug = slugify(text);
+	let mut anchor = slug.clone();
+	let mut n = 0;
+	while taken.contains(&anchor) {
+		n += 1;
+		anchor = format!("{slug}-{n}");
+	}
+	taken.insert(anchor.clone());
+	anchor
+}
+
+/// Lowercase alphanumerics, with whitespace, `-`, and `_` as single `-` (e.g., `"What's New?"` -> `whats-new`).
+fn slugify(text: &str) -> String {
+	let mut slug = String::with_capacity(text.len());
+	for c in text.chars().flat_map(char::to_lowercase) {
+		if c.is_alphanumeric() {
+			slug.push(c);
+		} else if (c.is_whitespace() || c == '-' || c == '_') && !slug.is_empty() && !slug.ends_with('-') {
+			slug.push('-');
+		}
+	}
+	let slug = slug.trim_end_matches('-');
+	if slug.is_empty() {
+		"section".to_string()
+	} else {
+		slug.to_string()
+	}
+}
+
+// endregion: --- Support
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	type TestResult<T> = core::result::Result<T, Box<dyn std::error::Error>>;
+
+	use super::*;
+
+	#[test]
+	fn test_outline_outline_tree_and_anchors() -> TestResult<()> {
+		// -- Setup & Fixtures
+		let fx_html = r#"
+		<html>
+		<head><title>Guide</title></head>
+		<body>
+			<h1>User   Guide</h1>
+			<h2 id="intro">Introduction</h2>
+			<h2>Getting Started</h2>
+			<h4>What's New?</h4>
+			<h3><a id="setup-anchor"></a>Setup</h3>
+			<h2>Getting Started</h2>
+			<h2> </h2>
+			<template><h2>Template</h2></template>
+			<h1>Appendix</h1>
+			<div id="faq"></div>
+			<h3>FAQ</h3>
+		</body>
+		</html>
+		"#;
+
+		// -- Exec
+		let headings = outline(fx_html)?;
+
+		// -- Check
+		assert_eq!(headings.len(), 2);
+		let guide = &headings[0];
+		assert_eq!(
+			(guide.level, guide.text.as_str(), guide.anchor.as_str()),
+			(1, "User Guide", "user-guide")
+		);
+		let sections: Vec<(&str, Option<&str>, &str)> = guide
+			.children
+			.iter()
+			.map(|h| (h.text.as_str(), h.id.as_deref(), h.anchor.as_str()))
+			.collect();
+		assert_eq!(
+			sections,
+			vec![
+				("Introduction", Some("intro"), "intro"),
+				("Getting Started", None, "getting-started"),
+				("Getting Started", None, "getting-started-1"),
+			]
+		);
+		let started = &guide.children[1];
+		let nested: Vec<(u8, &str)> = started.children.iter().map(|h| (h.level, h.anchor.as_str())).collect();
+		assert_eq!(nested, vec![(4, "whats-new"), (3, "setup-anchor")]);
+
+		let appendix = &headings[1];
+		assert_eq!(appendix.children[0].anchor, "faq-1");
+		assert!(appendix.children[0].is_generated_anchor());
+		assert_eq!(guide.flatten().len(), 6);
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
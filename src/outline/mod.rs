@@ -0,0 +1,9 @@
+// region:    --- Modules
+
+mod extract_outline;
+mod outline_heading;
+
+pub use extract_outline::*;
+pub use outline_heading::*;
+
+// endregion: --- Modules
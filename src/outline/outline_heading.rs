@@ -0,0 +1,43 @@
+use serde::{Deserialize, Serialize};
+
+// region:    --- Types
+
+/// A heading of the document outline, with its sub headings (see `outline`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OutlineHeading {
+	/// The heading level, from 1 (`<h1>`) to 6 (`<h6>`).
+	pub level: u8,
+	/// The whitespace collapsed text.
+	pub text: String,
+	/// The `id` of the heading (or of its first descendant with an `id`, e.g., `<h2><a id="install">`).
+	pub id: Option<String>,
+	/// The fragment to link to the heading: the `id`, else a slug generated from the text,
+	/// unique in the document (e.g., `getting-started`, `getting-started-1`).
+	pub anchor: String,
+	/// The headings of a lower level, up to the next heading of the same or a higher level.
+	pub children: Vec<OutlineHeading>,
+}
+
+// endregion: --- Types
+
+// region:    --- Accessors
+
+impl OutlineHeading {
+	/// Whether the anchor was generated (the heading has no `id`).
+	pub fn is_generated_anchor(&self) -> bool {
+		self.id.is_none()
+	}
+
+	/// The headings of the tree, depth first (the heading first).
+	pub fn flatten(&self) -> Vec<&OutlineHeading> {
+		let mut headings = Vec::new();
+		let mut stack = vec![self];
+		while let Some(heading) = stack.pop() {
+			headings.push(heading);
+			stack.extend(heading.children.iter().rev());
+		}
+		headings
+	}
+}
+
+// endregion: --- Accessors
@@ -0,0 +1,11 @@
+use crate::Elem;
+use serde::{Deserialize, Serialize};
+
+/// The elements matched by one selector of a `select_grouped` call.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ElemGroup {
+	/// The input selector (trimmed).
+	pub selector: String,
+	/// The matched elements, in document order.
+	pub elems: Vec<Elem>,
+}
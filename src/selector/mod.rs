@@ -1,7 +1,15 @@
 // region:    --- Modules
 
+mod elem_group;
 mod select;
+mod select_options;
+mod selector_set;
 
-pub use select::select;
+pub use elem_group::*;
+pub use select::{select, select_grouped, select_with_options};
+pub use select_options::*;
+pub use selector_set::*;
+
+pub(crate) use select::{build_selector, select_from_document, select_grouped_from_document};
 
 // endregion: --- Modules
@@ -1,5 +1,5 @@
-use crate::{Elem, Error, Result};
-use scraper::{Html, Selector};
+use crate::{Document, Elem, ElemGroup, Result, SelectOptions, SelectorSet};
+use scraper::Selector;
 
 /// Selects HTML elements based on a list of CSS selectors and returns them as a list of `Elem`.
 /// The selectors are combined with a comma, effectively performing an "OR" match.
@@ -14,43 +14,74 @@ use scraper::{Html, Selector};
 ///
 /// A `Result` containing:
 /// - `Ok(Vec<Elem>)`: A vector of `Elem` objects representing the selected elements.
-/// - `Err(Error)`: An error if parsing the HTML or the combined selector fails.
+/// - `Err(Error)`: An `Error::SelectorParse` with the first invalid selector of the list.
 pub fn select<S>(html_content: &str, selectors: S) -> Result<Vec<Elem>>
 where
 	S: IntoIterator,
 	S::Item: AsRef<str>,
 {
-	// -- Build the selectors_str
-	let mut selectors_str = String::new();
-	for s_ref in selectors {
-		let s = s_ref.as_ref().trim();
-		if s.is_empty() {
-			continue;
-		}
-		if !selectors_str.is_empty() {
-			selectors_str.push(',');
-		}
-		selectors_str.push_str(s);
-	}
-	// if empty, just return empty vector
-	if selectors_str.is_empty() {
-		return Ok(Vec::new());
-	}
-	// build the scraper seletor
-	let css_selector = Selector::parse(&selectors_str).map_err(|err| Error::SelectorParse {
-		selector: selectors_str.clone(),
-		cause: err.to_string(),
-	})?;
-
-	// -- Parse and select
-	let html = Html::parse_document(html_content);
-
-	let mut els = Vec::new();
-	for element_ref in html.select(&css_selector) {
-		els.push(Elem::from_element_ref(element_ref));
-	}
+	select_with_options(html_content, selectors, SelectOptions::default())
+}
+
+/// Same as `select`, with `SelectOptions` controlling how each `Elem` is built
+/// (e.g., `ElemTextMode::Rendered` for layout-aware `Elem::text`).
+pub fn select_with_options<S>(html_content: &str, selectors: S, options: impl Into<SelectOptions>) -> Result<Vec<Elem>>
+where
+	S: IntoIterator,
+	S::Item: AsRef<str>,
+{
+	let options = options.into();
+	Document::parse_with_mode(html_content, &options.parse_mode).select_with_options(selectors, options)
+}
 
-	Ok(els)
+/// Selects HTML elements like `select`, but returns them grouped by input selector,
+/// so callers can tell which selector matched each `Elem`.
+///
+/// - One `ElemGroup` per non-empty selector, in input order.
+/// - Each group is in document order.
+/// - An element matching several selectors is in each of their groups.
+pub fn select_grouped<S>(html_content: &str, selectors: S) -> Result<Vec<ElemGroup>>
+where
+	S: IntoIterator,
+	S::Item: AsRef<str>,
+{
+	Document::parse(html_content).select_grouped(selectors)
+}
+
+/// Selects the elements of an already parsed document (see `Document::select_with_options`).
+pub(crate) fn select_from_document<S>(doc: &Document, selectors: S, options: &SelectOptions) -> Result<Vec<Elem>>
+where
+	S: IntoIterator,
+	S::Item: AsRef<str>,
+{
+	let selector_set = SelectorSet::new(selectors)?;
+	Ok(selector_set.select_in_doc(doc, options))
+}
+
+/// Selects the elements of an already parsed document, grouped by selector (see `Document::select_grouped`).
+pub(crate) fn select_grouped_from_document<S>(
+	doc: &Document,
+	selectors: S,
+	options: &SelectOptions,
+) -> Result<Vec<ElemGroup>>
+where
+	S: IntoIterator,
+	S::Item: AsRef<str>,
+{
+	let selector_set = SelectorSet::new(selectors)?;
+	Ok(selector_set.select_grouped_in_doc(doc, options))
+}
+
+/// Parses the selectors and combines them into a single `scraper::Selector` (see `SelectorSet`).
+///
+/// Empty selector strings are ignored. Returns `Ok(None)` when no selector remains.
+pub(crate) fn build_selector<S>(selectors: S) -> Result<Option<Selector>>
+where
+	S: IntoIterator,
+	S::Item: AsRef<str>,
+{
+	let selector_set = SelectorSet::new(selectors)?;
+	Ok(selector_set.combined().cloned())
 }
 
 // region:    --- Tests
@@ -58,6 +89,7 @@ where
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use crate::{ElemTextMode, ParseMode, TextOptions};
 	// General test functions use this local `Result<T>` for `Box<dyn Error>`.
 	type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>;
 
@@ -265,6 +297,10 @@ mod tests {
 		let err_string = err.to_string();
 		// scraper's error for "p[" is "Invalid selector: Expected an attribute name, found Eof"
 		assert!(err_string.contains("is invalid"));
+		assert!(
+			err_string.starts_with("Selector 'h1[' is invalid"),
+			"Should report the individual selector"
+		);
 
 		Ok(())
 	}
@@ -353,6 +389,182 @@ mod tests {
 
 		Ok(())
 	}
+
+	#[test]
+	fn test_selector_select_with_options_rendered_text() -> Result<()> {
+		// -- Setup & Fixtures
+		let html_content = "<ul><li>A</li><li>B</li></ul>";
+		let options = SelectOptions::default().with_text_mode(ElemTextMode::Rendered(TextOptions::default()));
+
+		// -- Exec
+		let raw = select(html_content, ["ul"])?;
+		let rendered = select_with_options(html_content, ["ul"], options)?;
+
+		// -- Check
+		assert_eq!(raw[0].text.as_deref(), Some("AB"));
+		assert_eq!(rendered[0].text.as_deref(), Some("A\nB"));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_selector_select_grouped() -> Result<()> {
+		// -- Setup & Fixtures
+		let html_content = r#"
+            <h1>Title 1</h1>
+            <p class="a">Paragraph 1</p>
+            <h2>Title 2</h2>
+            <p>Paragraph 2</p>
+        "#;
+
+		// -- Exec
+		let groups = select_grouped(html_content, ["p", "", "h1, h2", "p.a", "h3"])?;
+
+		// -- Check
+		let summary: Vec<(&str, Vec<&str>)> = groups
+			.iter()
+			.map(|g| {
+				let texts = g.elems.iter().filter_map(|el| el.text.as_deref()).collect();
+				(g.selector.as_str(), texts)
+			})
+			.collect();
+		assert_eq!(
+			summary,
+			vec![
+				("p", vec!["Paragraph 1", "Paragraph 2"]),
+				("h1, h2", vec!["Title 1", "Title 2"]),
+				("p.a", vec!["Paragraph 1"]),
+				("h3", vec![]),
+			]
+		);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_selector_select_with_options_outer_html_and_children() -> Result<()> {
+		// -- Setup & Fixtures
+		let html_content = r#"<div id="a">Hi <b>there <i>you</i></b> </div>"#;
+		let options = SelectOptions::default().with_outer_html(true).with_children_depth(1);
+
+		// -- Exec
+		let els = select_with_options(html_content, ["#a"], options)?;
+
+		// -- Check
+		let el = els.first().ok_or("Should have one elem")?;
+		assert_eq!(
+			el.outer_html.as_deref(),
+			Some(r#"<div id="a">Hi <b>there <i>you</i></b> </div>"#)
+		);
+		let value = serde_json::to_value(el)?;
+		assert_eq!(value["children"][0], serde_json::json!({"type": "text", "text": "Hi "}));
+		assert_eq!(value["children"][1]["type"], "element");
+		assert_eq!(value["children"][1]["tag"], "b");
+		assert!(
+			value["children"][1].get("outer_html").is_none(),
+			"Children have no html without with_children_html"
+		);
+		assert!(
+			value["children"][1].get("children").is_none(),
+			"Depth 1 should stop at direct children"
+		);
+		assert_eq!(value["children"][2], serde_json::json!({"type": "text", "text": " "}));
+		assert_eq!(value["children"].as_array().map(|a| a.len()), Some(3));
+
+		// -- Exec & Check - With the children html
+		let options = SelectOptions::default()
+			.with_outer_html(true)
+			.with_children_depth(1)
+			.with_children_html(true);
+		let els = select_with_options(html_content, ["#a"], options)?;
+		let value = serde_json::to_value(&els[0])?;
+		assert_eq!(value["children"][1]["outer_html"], "<b>there <i>you</i></b>");
+		assert_eq!(value["children"][1]["inner_html"], "there <i>you</i>");
+
+		// -- Exec & Check - Default select has no outer_html/children (and does not serialize them)
+		let els = select(html_content, ["#a"])?;
+		let value = serde_json::to_value(&els[0])?;
+		assert!(value.get("outer_html").is_none());
+		assert!(value.get("children").is_none());
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_selector_select_with_options_source_spans() -> Result<()> {
+		// -- Setup & Fixtures
+		let html_content = "<html>\n<body>\n  <p>One</p>\n  <p class=\"x\">Two <b>b</b></p>\n</body>\n</html>";
+		let options = SelectOptions::default().with_source_spans(true);
+
+		// -- Exec
+		let els = select_with_options(html_content, ["p.x", "b"], options)?;
+
+		// -- Check
+		let span = els[0].span.ok_or("Should have a span")?;
+		assert_eq!(&html_content[span.start..span.end], r#"<p class="x">Two <b>b</b></p>"#);
+		assert_eq!((span.line, span.column), (4, 3));
+		let span = els[1].span.ok_or("Should have a span")?;
+		assert_eq!(&html_content[span.start..span.end], "<b>b</b>");
+		// No span by default
+		assert!(select(html_content, ["p"])?[0].span.is_none());
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_selector_select_with_options_fragment_context() -> Result<()> {
+		// -- Setup & Fixtures
+		let html_content = r#"<td class="a">A</td><td>B</td>"#;
+
+		// -- Exec
+		let in_document = select(html_content, ["td"])?;
+		let in_body = select_with_options(
+			html_content,
+			["td", "html"],
+			SelectOptions::default().with_parse_mode(ParseMode::fragment()),
+		)?;
+		let in_row = select_with_options(
+			html_content,
+			["td", "html"],
+			SelectOptions::default().with_parse_mode(ParseMode::fragment_in("tr")),
+		)?;
+
+		// -- Check
+		// In a document or body context, the parser drops the `<td>` tags
+		assert!(in_document.is_empty());
+		assert!(in_body.is_empty(), "The synthetic <html> root should not be selected");
+		assert_eq!(in_row.len(), 2);
+		assert_eq!(in_row[0].text.as_deref(), Some("A"));
+		assert_eq!(in_row[1].inner_html.as_deref(), Some("B"));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_selector_select_with_options_base_url() -> Result<()> {
+		// -- Setup & Fixtures
+		let html_content = r#"<div id="a"><a href="../b.html">B</a><img src="/c.png"></div>"#;
+		let options = SelectOptions::default()
+			.with_base_url("https://example.com/docs/a/")
+			.with_children_depth(1);
+
+		// -- Exec
+		let els = select_with_options(html_content, ["a", "#a"], options)?;
+
+		// -- Check
+		let link = els.iter().find(|el| el.tag == "a").ok_or("Should have a link")?;
+		let href = link
+			.attrs
+			.as_ref()
+			.and_then(|attrs| attrs.get("href"))
+			.map(String::as_str);
+		assert_eq!(href, Some("https://example.com/docs/b.html"));
+		let div = els.iter().find(|el| el.tag == "div").ok_or("Should have a div")?;
+		let value = serde_json::to_value(div)?;
+		assert_eq!(value["children"][1]["attrs"]["src"], "https://example.com/c.png");
+
+		Ok(())
+	}
 }
 
 // endregion: --- Tests
@@ -0,0 +1,94 @@
+use crate::{AttrsOrder, ParseMode, TextOptions};
+
+// region:    --- Types
+
+/// Options for the `select_with_options` function (how each `Elem` is built).
+///
+/// The default builds the `Elem` as `select` does (raw text, no `outer_html`, no `children`).
+#[derive(Clone, Debug, Default)]
+pub struct SelectOptions {
+	/// How `Elem::text` is computed.
+	pub text_mode: ElemTextMode,
+	/// Whether `Elem::outer_html` is set.
+	pub outer_html: bool,
+	/// The number of `Elem::children` levels to build (`None` for no children, `Some(usize::MAX)` for the full subtree).
+	pub children_depth: Option<usize>,
+	/// Whether the `Elem::children` elements have their `inner_html` (and `outer_html`), which is quadratic
+	/// in the subtree size (not set by default).
+	pub children_html: bool,
+	/// The order of `Elem::attrs` (source order by default).
+	pub attrs_order: AttrsOrder,
+	/// Whether `Elem::span` is set.
+	pub source_spans: bool,
+	/// The absolute base URL the `href`, `src`, `srcset`, and `action` of `Elem::attrs` are resolved against
+	/// (`None` to keep them as is). A `<base href>` of the document takes precedence (resolved against it).
+	pub base_url: Option<String>,
+	/// How `select_with_options` parses the HTML (full document by default). Not used by `Document` (already parsed).
+	pub parse_mode: ParseMode,
+}
+
+/// How `Elem::text` is computed.
+#[derive(Clone, Copy, Debug, Default)]
+pub enum ElemTextMode {
+	/// Concatenation of all the descendant text nodes, as is (e.g., `<li>A</li><li>B</li>` gives `AB`).
+	#[default]
+	Raw,
+	/// Layout-aware text, as rendered by `to_text` (e.g., `<li>A</li><li>B</li>` gives `A\nB`).
+	Rendered(TextOptions),
+}
+
+// endregion: --- Types
+
+// region:    --- Constructors & Fluid API
+
+impl SelectOptions {
+	/// Set how `Elem::text` is computed.
+	pub fn with_text_mode(mut self, text_mode: ElemTextMode) -> Self {
+		self.text_mode = text_mode;
+		self
+	}
+
+	/// Set `Elem::outer_html` on the selected elements.
+	pub fn with_outer_html(mut self, outer_html: bool) -> Self {
+		self.outer_html = outer_html;
+		self
+	}
+
+	/// Build `Elem::children` down to the given depth (`1` for the direct children only, `usize::MAX` for all).
+	pub fn with_children_depth(mut self, depth: usize) -> Self {
+		self.children_depth = Some(depth);
+		self
+	}
+
+	/// Set `inner_html` (and `outer_html`, with `with_outer_html`) on the `Elem::children` elements.
+	pub fn with_children_html(mut self, children_html: bool) -> Self {
+		self.children_html = children_html;
+		self
+	}
+
+	/// Set the order of `Elem::attrs`.
+	pub fn with_attrs_order(mut self, attrs_order: AttrsOrder) -> Self {
+		self.attrs_order = attrs_order;
+		self
+	}
+
+	/// Set `Elem::span` (the element location in the HTML source) on the selected elements.
+	pub fn with_source_spans(mut self, source_spans: bool) -> Self {
+		self.source_spans = source_spans;
+		self
+	}
+
+	/// Set the base URL to resolve the relative URL attributes against (e.g., the page URL).
+	pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+		self.base_url = Some(base_url.into());
+		self
+	}
+
+	/// Set how `select_with_options` parses the HTML (e.g., `ParseMode::fragment_in("tr")` for `<td>` snippets).
+	pub fn with_parse_mode(mut self, parse_mode: ParseMode) -> Self {
+		self.parse_mode = parse_mode;
+		self
+	}
+}
+
+// endregion: --- Constructors & Fluid API
@@ -0,0 +1,217 @@
+use crate::url::effective_base_url;
+use crate::{Document, Elem, ElemGroup, Error, Result, SelectOptions};
+use scraper::Selector;
+
+/// A list of CSS selectors parsed once, reusable across documents and threads (`Send + Sync`).
+///
+/// Each selector is validated individually, so an invalid one is reported as is in `Error::SelectorParse`
+/// (not as the combined comma string). The selectors are matched with an "OR" logic, in document order,
+/// exactly like `select`.
+#[derive(Debug, Clone)]
+pub struct SelectorSet {
+	/// The individual (trimmed, non-empty) selectors, in input order.
+	selectors: Vec<String>,
+	/// The individual parsed selectors (same order as `selectors`).
+	parsed: Vec<Selector>,
+	/// All the selectors combined with a comma (`None` when empty).
+	combined: Option<Selector>,
+}
+
+// region:    --- Constructors
+
+impl SelectorSet {
+	/// Parses the list of CSS selectors. Empty selector strings are ignored.
+	///
+	/// Returns `Error::SelectorParse` for the first invalid selector of the list.
+	pub fn new<S>(selectors: S) -> Result<Self>
+	where
+		S: IntoIterator,
+		S::Item: AsRef<str>,
+	{
+		let mut strs = Vec::new();
+		let mut parsed = Vec::new();
+		for s_ref in selectors {
+			let s = s_ref.as_ref().trim();
+			if s.is_empty() {
+				continue;
+			}
+			let selector = Selector::parse(s).map_err(|err| Error::SelectorParse {
+				selector: s.to_string(),
+				cause: err.to_string(),
+			})?;
+			strs.push(s.to_string());
+			parsed.push(selector);
+		}
+
+		let combined = if strs.is_empty() {
+			None
+		} else {
+			let combined_str = strs.join(",");
+			let combined = Selector::parse(&combined_str).map_err(|err| Error::SelectorParse {
+				selector: combined_str.clone(),
+				cause: err.to_string(),
+			})?;
+			Some(combined)
+		};
+
+		Ok(Self {
+			selectors: strs,
+			parsed,
+			combined,
+		})
+	}
+}
+
+// endregion: --- Constructors
+
+// region:    --- Select
+
+impl SelectorSet {
+	/// Selects the matching elements of the document, in document order.
+	pub fn select(&self, doc: &Document) -> Vec<Elem> {
+		self.select_in_doc(doc, &SelectOptions::default())
+	}
+
+	/// Selects the matching elements of the document, with `SelectOptions`.
+	pub fn select_with_options(&self, doc: &Document, options: impl Into<SelectOptions>) -> Vec<Elem> {
+		self.select_in_doc(doc, &options.into())
+	}
+
+	pub(crate) fn select_in_doc(&self, doc: &Document, options: &SelectOptions) -> Vec<Elem> {
+		let Some(combined) = &self.combined else {
+			return Vec::new();
+		};
+		let source_map = options.source_spans.then(|| doc.source_map());
+		let base_url = options
+			.base_url
+			.as_deref()
+			.map(|base_url| effective_base_url(doc.html(), base_url));
+		doc.html()
+			.select(combined)
+			.filter(|el| !doc.is_synthetic_root(el))
+			.map(|el| Elem::from_element_ref(el, options, source_map, base_url.as_deref()))
+			.collect()
+	}
+
+	/// Selects the matching elements of the document, grouped by selector (in the set order).
+	///
+	/// Each group keeps the document order, and an element matching several selectors is in each of their groups.
+	pub fn select_grouped(&self, doc: &Document) -> Vec<ElemGroup> {
+		self.select_grouped_in_doc(doc, &SelectOptions::default())
+	}
+
+	/// Same as `select_grouped`, with `SelectOptions`.
+	pub fn select_grouped_with_options(&self, doc: &Document, options: impl Into<SelectOptions>) -> Vec<ElemGroup> {
+		self.select_grouped_in_doc(doc, &options.into())
+	}
+
+	pub(crate) fn select_grouped_in_doc(&self, doc: &Document, options: &SelectOptions) -> Vec<ElemGroup> {
+		let source_map = options.source_spans.then(|| doc.source_map());
+		let base_url = options
+			.base_url
+			.as_deref()
+			.map(|base_url| effective_base_url(doc.html(), base_url));
+		self.selectors
+			.iter()
+			.zip(self.parsed.iter())
+			.map(|(selector, parsed)| ElemGroup {
+				selector: selector.clone(),
+				elems: doc
+					.html()
+					.select(parsed)
+					.filter(|el| !doc.is_synthetic_root(el))
+					.map(|el| Elem::from_element_ref(el, options, source_map, base_url.as_deref()))
+					.collect(),
+			})
+			.collect()
+	}
+}
+
+// endregion: --- Select
+
+// region:    --- Accessors
+
+impl SelectorSet {
+	/// The individual selectors, in input order (empty ones removed).
+	pub fn selectors(&self) -> impl Iterator<Item = &str> {
+		self.selectors.iter().map(|s| s.as_str())
+	}
+
+	/// The combined `scraper::Selector` (`None` when the set is empty).
+	pub(crate) fn combined(&self) -> Option<&Selector> {
+		self.combined.as_ref()
+	}
+
+	pub fn len(&self) -> usize {
+		self.selectors.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.selectors.is_empty()
+	}
+}
+
+// endregion: --- Accessors
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	type TestResult<T> = core::result::Result<T, Box<dyn std::error::Error>>;
+
+	use super::*;
+
+	#[test]
+	fn test_selector_set_is_send_sync() -> TestResult<()> {
+		fn assert_send_sync<T: Send + Sync>() {}
+		assert_send_sync::<SelectorSet>();
+		Ok(())
+	}
+
+	#[test]
+	fn test_selector_set_reuse_across_documents_and_threads() -> TestResult<()> {
+		// -- Setup & Fixtures
+		let set = std::sync::Arc::new(SelectorSet::new(["h1", "", "p.x"])?);
+
+		// -- Exec
+		let handles: Vec<_> = ["<h1>A</h1><p class='x'>B</p>", "<p class='x'>C</p><p>D</p>"]
+			.into_iter()
+			.map(|html| {
+				let set = set.clone();
+				std::thread::spawn(move || {
+					let doc = Document::parse(html);
+					set.select(&doc)
+						.into_iter()
+						.filter_map(|el| el.text)
+						.collect::<Vec<_>>()
+				})
+			})
+			.collect();
+		let results = handles
+			.into_iter()
+			.map(|h| h.join().map_err(|_| "thread panicked"))
+			.collect::<core::result::Result<Vec<_>, _>>()?;
+
+		// -- Check
+		assert_eq!(set.selectors().collect::<Vec<_>>(), vec!["h1", "p.x"]);
+		assert_eq!(results, vec![vec!["A", "B"], vec!["C"]]);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_selector_set_reports_individual_invalid_selector() -> TestResult<()> {
+		// -- Exec
+		let res = SelectorSet::new(["p", "h1[", "div"]);
+
+		// -- Check
+		let Err(Error::SelectorParse { selector, .. }) = res else {
+			panic!("Should have been a SelectorParse error")
+		};
+		assert_eq!(selector, "h1[");
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
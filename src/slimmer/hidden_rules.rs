@@ -0,0 +1,134 @@
+use scraper::node::Element;
+
+// region:    --- Types
+
+/// The rules removing the content never visible to users (body only), each enabled individually.
+///
+/// All the rules are disabled by default (see `SlimOptions::with_hidden_rules`).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct HiddenRules {
+	/// Elements with the `hidden` attribute (except `hidden="until-found"`, which can be revealed by the page search).
+	pub hidden_attr: bool,
+	/// Elements with `aria-hidden="true"`.
+	pub aria_hidden: bool,
+	/// Elements with an inline `style` of `display: none` or `visibility: hidden`.
+	pub inline_style: bool,
+	/// `<template>` elements (inert content).
+	pub template: bool,
+	/// `<noscript>` elements (usually duplicates of the scripted content).
+	pub noscript: bool,
+	/// `<input type="hidden">` elements.
+	pub hidden_inputs: bool,
+}
+
+// endregion: --- Types
+
+// region:    --- Constructors & Fluid API
+
+impl HiddenRules {
+	/// All the rules enabled.
+	pub fn all() -> Self {
+		Self {
+			hidden_attr: true,
+			aria_hidden: true,
+			inline_style: true,
+			template: true,
+			noscript: true,
+			hidden_inputs: true,
+		}
+	}
+
+	pub fn with_hidden_attr(mut self, enabled: bool) -> Self {
+		self.hidden_attr = enabled;
+		self
+	}
+
+	pub fn with_aria_hidden(mut self, enabled: bool) -> Self {
+		self.aria_hidden = enabled;
+		self
+	}
+
+	pub fn with_inline_style(mut self, enabled: bool) -> Self {
+		self.inline_style = enabled;
+		self
+	}
+
+	pub fn with_template(mut self, enabled: bool) -> Self {
+		self.template = enabled;
+		self
+	}
+
+	pub fn with_noscript(mut self, enabled: bool) -> Self {
+		self.noscript = enabled;
+		self
+	}
+
+	pub fn with_hidden_inputs(mut self, enabled: bool) -> Self {
+		self.hidden_inputs = enabled;
+		self
+	}
+}
+
+// endregion: --- Constructors & Fluid API
+
+// region:    --- Matching
+
+impl HiddenRules {
+	/// Whether the element is hidden according to the enabled rules.
+	pub(crate) fn is_hidden(&self, element: &Element) -> bool {
+		let tag_name = element.name();
+
+		(self.template && tag_name == "template")
+			|| (self.noscript && tag_name == "noscript")
+			|| (self.hidden_inputs
+				&& tag_name == "input"
+				&& element
+					.attr("type")
+					.is_some_and(|t| t.trim().eq_ignore_ascii_case("hidden")))
+			|| (self.hidden_attr
+				&& element
+					.attr("hidden")
+					.is_some_and(|v| !v.trim().eq_ignore_ascii_case("until-found")))
+			|| (self.aria_hidden
+				&& element
+					.attr("aria-hidden")
+					.is_some_and(|v| v.trim().eq_ignore_ascii_case("true")))
+			|| (self.inline_style && element.attr("style").is_some_and(is_style_hidden))
+	}
+}
+
+/// Whether an inline style declares `display: none` or `visibility: hidden`.
+fn is_style_hidden(style: &str) -> bool {
+	style.split(';').any(|declaration| {
+		let Some((property, value)) = declaration.split_once(':') else {
+			return false;
+		};
+		let property = property.trim().to_ascii_lowercase();
+		let value = value.trim().trim_end_matches("!important").trim().to_ascii_lowercase();
+		(property == "display" && value == "none") || (property == "visibility" && value == "hidden")
+	})
+}
+
+// endregion: --- Matching
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	type TestResult<T> = core::result::Result<T, Box<dyn std::error::Error>>;
+
+	use super::*;
+
+	#[test]
+	fn test_hidden_rules_is_style_hidden() -> TestResult<()> {
+		// -- Check
+		assert!(is_style_hidden("color: red; DISPLAY : None"));
+		assert!(is_style_hidden("visibility:hidden !important"));
+		assert!(!is_style_hidden("display: block; visibility: visible"));
+		assert!(!is_style_hidden("content: 'display:none'x"));
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
@@ -1,10 +1,22 @@
 // region:    --- Modules
 
 mod support;
+mod hidden_rules;
+mod name_set;
 mod slim;
+mod slim_budget;
 mod slim_options;
+mod slim_output;
+mod slim_preset;
+mod slim_rule;
 
+pub use hidden_rules::*;
+pub use name_set::*;
 pub use slim::*;
+pub use slim_budget::*;
 pub use slim_options::*;
+pub use slim_output::*;
+pub use slim_preset::*;
+pub use slim_rule::*;
 
 // endregion: --- Modules
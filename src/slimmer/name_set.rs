@@ -0,0 +1,132 @@
+use std::collections::BTreeSet;
+
+/// A case-insensitive set of tag, attribute, or keyword names used by the `slim` policies.
+///
+/// Names are stored lowercased and iterated in sorted order, so two equal sets always
+/// behave (and debug-print) the same way.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct NameSet {
+	names: BTreeSet<String>,
+}
+
+// region:    --- Constructors
+
+impl NameSet {
+	/// Creates a set from any list of names.
+	pub fn new<I>(names: I) -> Self
+	where
+		I: IntoIterator,
+		I::Item: AsRef<str>,
+	{
+		let names = names.into_iter().map(|n| n.as_ref().trim().to_lowercase()).collect();
+		Self { names }
+	}
+
+	/// Creates a set from a static list (used for the crate defaults).
+	pub(crate) fn from_static(names: &[&str]) -> Self {
+		Self::new(names.iter())
+	}
+}
+
+impl<S: AsRef<str>> FromIterator<S> for NameSet {
+	fn from_iter<T: IntoIterator<Item = S>>(iter: T) -> Self {
+		Self::new(iter)
+	}
+}
+
+// endregion: --- Constructors
+
+// region:    --- Edits
+
+impl NameSet {
+	/// Returns this set with the given names added.
+	pub fn extended<I>(mut self, names: I) -> Self
+	where
+		I: IntoIterator,
+		I::Item: AsRef<str>,
+	{
+		self.names.extend(names.into_iter().map(|n| n.as_ref().trim().to_lowercase()));
+		self
+	}
+
+	/// Returns this set with the given names removed.
+	pub fn subtracted<I>(mut self, names: I) -> Self
+	where
+		I: IntoIterator,
+		I::Item: AsRef<str>,
+	{
+		for name in names {
+			self.names.remove(&name.as_ref().trim().to_lowercase());
+		}
+		self
+	}
+}
+
+// endregion: --- Edits
+
+// region:    --- Accessors
+
+impl NameSet {
+	/// Returns `true` if the set contains the name (case-insensitive).
+	pub fn contains(&self, name: &str) -> bool {
+		if self.names.contains(name) {
+			return true;
+		}
+		// Only pay for the lowercase allocation when the name is not already lowercase.
+		name.bytes().any(|b| b.is_ascii_uppercase()) && self.names.contains(&name.to_lowercase())
+	}
+
+	/// Iterates over the names in sorted order.
+	pub fn iter(&self) -> impl Iterator<Item = &str> {
+		self.names.iter().map(|s| s.as_str())
+	}
+
+	pub fn len(&self) -> usize {
+		self.names.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.names.is_empty()
+	}
+}
+
+// endregion: --- Accessors
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	type TestResult<T> = core::result::Result<T, Box<dyn std::error::Error>>;
+
+	use super::*;
+
+	#[test]
+	fn test_name_set_case_insensitive() -> TestResult<()> {
+		// -- Setup & Fixtures
+		let set = NameSet::new(["Alt", " src "]);
+
+		// -- Check
+		assert!(set.contains("alt"));
+		assert!(set.contains("ALT"));
+		assert!(set.contains("src"));
+		assert!(!set.contains("href"));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_name_set_extended_and_subtracted() -> TestResult<()> {
+		// -- Setup & Fixtures
+		let set = NameSet::new(["class", "id"]);
+
+		// -- Exec
+		let set = set.extended(["alt", "lang"]).subtracted(["ID"]);
+
+		// -- Check
+		assert_eq!(set.iter().collect::<Vec<_>>(), vec!["alt", "class", "lang"]);
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
@@ -1,12 +1,18 @@
-use super::SlimOptions;
+use super::slim_budget::slim_within_budget;
+use super::{SlimAction, SlimElement, SlimMapping, SlimOptions, SlimOutput};
 use crate::error::{Error, Result};
-use ego_tree::NodeRef;
+use crate::source::SourceMap;
+use crate::url::effective_base_url;
+use crate::{Document, build_selector};
+use ego_tree::{NodeId, NodeRef};
 use scraper::{ElementRef, Html, node::Node};
+use std::collections::HashSet;
 
 use super::support::{
-	BLOCK_LEVEL_TAGS, REMOVABLE_EMPTY_TAGS, TAGS_TO_REMOVE, VOID_ELEMENTS, filter_and_write_attributes,
-	is_string_effectively_empty, remove_empty_lines, should_keep_meta,
+	filter_and_write_attributes, is_string_effectively_empty, remove_empty_lines, remove_indent_level,
+	should_keep_meta, truncate_text,
 };
+use crate::support::{BLOCK_LEVEL_TAGS, INLINE_FLOW_TAGS, VOID_ELEMENTS};
 
 /// Decodes HTML entities (e.g., `&lt;` becomes `<`).
 /// Re-exporting from the original slimmer or using html-escape directly.
@@ -18,18 +24,40 @@ pub fn decode_html_entities(content: &str) -> String {
 /// preserving essential head tags, and returns the cleaned HTML as a string.
 ///
 /// This function aims to replicate the behavior of `slimmer::slim` using `scraper`.
-/// It removes:
-/// - Non-visible tags like `<script>`, `<link>`, `<style>`, `<svg>`, `<base>`.
+/// With the default `SlimOptions`, it removes:
+/// - Non-visible tags like `<script>`, `<link>`, `<style>`, `<svg>`, `<base>` (`SlimOptions::tags_to_remove`).
 /// - HTML comments.
 /// - Empty or whitespace-only text nodes.
-/// - Specific tags (like `<div>`, `<span>`, `<p>`, etc.) if they become effectively empty *after* processing children.
-/// - Attributes except for specific allowlists (`class`, `aria-label`, `href` outside head; `property`, `content` for relevant meta tags in head).
+/// - Specific tags (like `<div>`, `<span>`, `<p>`, etc.) if they become effectively empty *after* processing children
+///   (`SlimOptions::removable_empty_tags`).
+/// - Attributes except for specific allowlists (`class`, `aria-label`, `href`, `title`, `id` outside head;
+///   `property`, `content` for relevant meta tags in head) (`SlimOptions::allowed_body_attrs` and `allowed_meta_attrs`).
+///
+/// With `SlimOptions::base_url`, the kept URL attributes (`href`, `src`, `srcset`, `action`) are made absolute.
 ///
 /// It preserves:
 /// - `<title>` tag within `<head>`.
-/// - `<meta>` tags within `<head>` if their `property` attribute matches keywords in `META_PROPERTY_KEYWORDS`.
+/// - `<meta>` tags within `<head>` if their `property` attribute matches `SlimOptions::meta_property_keywords`.
 /// - Essential body content.
 ///
+/// With `SlimOptions::parse_mode` set to `ParseMode::Fragment`, the HTML is parsed as a snippet, and the output
+/// has no synthetic `<html>`/`<head>`/`<body>` wrappers.
+///
+/// Body content never visible to users (`hidden`, `aria-hidden="true"`, `display: none`, etc.) is removed
+/// per the enabled `SlimOptions::hidden_rules`.
+///
+/// Body elements matching `SlimOptions::drop_selectors` are removed, and when `SlimOptions::keep_selectors`
+/// is set, the body is scoped to the matching elements (plus the ancestors leading to them).
+///
+/// With `SlimOptions::with_unwrap_layout`, the layout-only `div`/`span` wrappers (no kept attribute, or a single
+/// child element) are replaced by their content.
+///
+/// Custom `SlimOptions::rules` (see `SlimRule`) can then drop, unwrap, rename, or rewrite the kept elements,
+/// texts, and attributes.
+///
+/// With `SlimOptions::token_budget`, stronger reductions are applied until the output fits
+/// (see `slim_with_report` for what was cut).
+///
 /// # Arguments
 ///
 /// * `html_content` - A string slice containing the HTML content to be processed.
@@ -38,18 +66,118 @@ pub fn decode_html_entities(content: &str) -> String {
 ///
 /// A `Result<String>` which is:
 /// - `Ok(String)` containing the cleaned HTML content.
-/// - `Err` if any errors occur during processing.
+/// - `Err` if any errors occur during processing (e.g., `Error::SelectorParse` for an invalid keep/drop selector).
 pub fn slim(html_content: &str, options: impl Into<SlimOptions>) -> Result<String> {
 	let options = options.into();
-	let html = Html::parse_document(html_content);
-	let mut output = String::new();
+	Document::parse_with_mode(html_content, &options.parse_mode).slim(options)
+}
+
+/// Same as `slim`, but also returns the mapping of the output elements back to the HTML source,
+/// so each slimmed piece can be traced to its origin (see `SlimOutput`).
+pub fn slim_mapped(html_content: &str, options: impl Into<SlimOptions>) -> Result<SlimOutput> {
+	let options = options.into();
+	Document::parse_with_mode(html_content, &options.parse_mode).slim_mapped(options)
+}
+
+/// Slims an already parsed HTML (see `Document::slim`).
+pub(crate) fn slim_html(html: &Html, options: &SlimOptions) -> Result<String> {
+	Ok(slim_html_mapped(html, options, None)?.html)
+}
+
+/// Slims an already parsed HTML, with the output mappings when a `source_map` is given.
+pub(crate) fn slim_html_mapped(
+	html: &Html,
+	options: &SlimOptions,
+	source_map: Option<&SourceMap>,
+) -> Result<SlimOutput> {
+	match &options.token_budget {
+		Some(budget) => Ok(slim_within_budget(html, options, budget, source_map)?.0),
+		None => Ok(slim_pass(html, options, source_map)?.0),
+	}
+}
+
+/// What a slim pass removed or kept (to report the cuts of the token budget reductions).
+#[derive(Debug, Clone, Copy, Default)]
+pub(super) struct SlimStats {
+	/// The attributes written.
+	pub attributes: usize,
+	/// The list items removed by `SlimOptions::max_list_items`.
+	pub list_items: usize,
+	/// The text nodes truncated by `SlimOptions::max_text_chars`.
+	pub truncated_texts: usize,
+	/// The elements removed by `SlimOptions::drop_selectors`.
+	pub dropped_elements: usize,
+}
+
+/// Slims an already parsed HTML once (without the token budget).
+pub(super) fn slim_pass(
+	html: &Html,
+	options: &SlimOptions,
+	source_map: Option<&SourceMap>,
+) -> Result<(SlimOutput, SlimStats)> {
+	let rules = SelectorRules::new(html, options)?;
+	let mut output = SlimOutput::default();
 
-	process_node_stack_based(html.tree.root(), false, &options, 0, &mut output)?;
+	let stats = process_node_stack_based(html.tree.root(), false, options, &rules, source_map, 0, &mut output)?;
 
 	// Final cleanup of empty lines
-	let content = remove_empty_lines(output)?;
+	let html = remove_empty_lines(output.html, &mut output.mappings)?;
+
+	let output = SlimOutput {
+		html,
+		mappings: output.mappings,
+	};
+	Ok((output, stats))
+}
+
+/// The `SlimOptions` keep/drop selectors (and base URL) resolved against a parsed document.
+struct SelectorRules {
+	drop_ids: HashSet<NodeId>,
+	/// `None` when there are no keep selectors (everything is in scope).
+	keep_scope: Option<KeepScope>,
+	/// The base URL of the document (`None` when the URLs are kept as is).
+	base_url: Option<String>,
+}
+
+struct KeepScope {
+	/// Elements matching the keep selectors (kept with all their content).
+	kept_ids: HashSet<NodeId>,
+	/// Ancestors of the kept elements (kept as structure only).
+	ancestor_ids: HashSet<NodeId>,
+}
+
+impl SelectorRules {
+	fn new(html: &Html, options: &SlimOptions) -> Result<Self> {
+		let drop_ids = match build_selector(&options.drop_selectors)? {
+			Some(selector) => html.select(&selector).map(|el| el.id()).collect(),
+			None => HashSet::new(),
+		};
+
+		let keep_scope = match build_selector(&options.keep_selectors)? {
+			Some(selector) => {
+				let kept_ids: HashSet<NodeId> = html.select(&selector).map(|el| el.id()).collect();
+				let mut ancestor_ids = HashSet::new();
+				for id in kept_ids.iter() {
+					if let Some(node) = html.tree.get(*id) {
+						ancestor_ids.extend(node.ancestors().map(|n| n.id()));
+					}
+				}
+				Some(KeepScope { kept_ids, ancestor_ids })
+			}
+			None => None,
+		};
+
+		let base_url = options
+			.base_url
+			.as_deref()
+			.map(|base_url| effective_base_url(html, base_url));
 
-	Ok(content)
+		Ok(Self {
+			drop_ids,
+			keep_scope,
+			base_url,
+		})
+	}
 }
 
 /// Non‑recursive stack‑based version of the slim processing.
@@ -57,9 +185,12 @@ fn process_node_stack_based(
 	root_node: NodeRef<Node>,
 	is_in_head_context: bool,
 	options: &SlimOptions,
+	rules: &SelectorRules,
+	source_map: Option<&SourceMap>,
 	depth: usize,
-	output: &mut String,
-) -> Result<()> {
+	output: &mut SlimOutput,
+) -> Result<SlimStats> {
+	let mut stats = SlimStats::default();
 	let indent_spaces = options.indent.unwrap_or(0) as usize;
 	let use_tabs = options.indent_with_tabs;
 
@@ -72,22 +203,100 @@ fn process_node_stack_based(
 	struct Frame<'a> {
 		node: NodeRef<'a, Node>,
 		is_in_head_context: bool,
+		/// Whether the node is inside the keep scope (always true without keep selectors).
+		is_in_keep_scope: bool,
 		depth: usize,
 		state: FrameState,
 		children_output: String,
+		/// The mappings of `children_output` (relative to its start).
+		children_mappings: Vec<SlimMapping>,
+		/// The tag of the element output (from `SlimAction::Rename`), when not its own.
+		rename: Option<String>,
+		/// The number of elements in `children_output` (for `SlimOptions::unwrap_tags`).
+		children_elements: usize,
+		/// Whether `children_output` has text outside of its elements.
+		has_children_text: bool,
+		/// Whether a space should separate the next content of `children_output` (after the text of an unwrapped
+		/// block container, in flat mode).
+		pending_space: bool,
 		/// Where this frame's output should be appended.
 		/// `Some(idx)` means the frame at the given stack index is the parent
 		/// that will collect our output; `None` means append to global output.
 		output_target_index: Option<usize>,
 	}
 
+	/// The global output, with its pending space (see `Frame::pending_space`).
+	struct GlobalOutput<'o> {
+		output: &'o mut SlimOutput,
+		pending_space: bool,
+	}
+
+	/// Appends the output (and its mappings, relative to its start) to the target frame, or to the global output.
+	///
+	/// `elements` and `has_text` describe the output content, for the target frame `children_elements`
+	/// and `has_children_text`.
+	fn append_output(
+		stack: &mut [Frame],
+		global: &mut GlobalOutput,
+		output_target_index: Option<usize>,
+		out: &str,
+		out_mappings: Vec<SlimMapping>,
+		elements: usize,
+		has_text: bool,
+	) {
+		let (html, mappings, pending_space) = match output_target_index {
+			Some(idx) => {
+				let target = stack.get_mut(idx).expect("target frame should exist");
+				target.children_elements += elements;
+				target.has_children_text |= has_text;
+				(
+					&mut target.children_output,
+					&mut target.children_mappings,
+					&mut target.pending_space,
+				)
+			}
+			None => (
+				&mut global.output.html,
+				&mut global.output.mappings,
+				&mut global.pending_space,
+			),
+		};
+		if *pending_space && !out.is_empty() {
+			*pending_space = false;
+			if !out.starts_with(char::is_whitespace) {
+				html.push(' ');
+			}
+		}
+		let base = html.len();
+		html.push_str(out);
+		mappings.extend(out_mappings.into_iter().map(|m| m.shifted(base)));
+	}
+
+	/// Sets the pending space of the target frame, or of the global output.
+	fn set_pending_space(stack: &mut [Frame], global: &mut GlobalOutput, output_target_index: Option<usize>) {
+		match output_target_index {
+			Some(idx) => stack.get_mut(idx).expect("target frame should exist").pending_space = true,
+			None => global.pending_space = true,
+		}
+	}
+
+	let mut global = GlobalOutput {
+		output,
+		pending_space: false,
+	};
 	let mut stack: Vec<Frame> = Vec::new();
 	stack.push(Frame {
 		node: root_node,
 		is_in_head_context,
+		is_in_keep_scope: rules.keep_scope.is_none(),
 		depth,
 		state: FrameState::Enter,
 		children_output: String::new(),
+		children_mappings: Vec::new(),
+		rename: None,
+		children_elements: 0,
+		has_children_text: false,
+		pending_space: false,
 		output_target_index: None,
 	});
 
@@ -102,9 +311,15 @@ fn process_node_stack_based(
 							stack.push(Frame {
 								node: child,
 								is_in_head_context: false,
+								is_in_keep_scope: frame.is_in_keep_scope,
 								depth: frame.depth,
 								state: FrameState::Enter,
 								children_output: String::new(),
+								children_mappings: Vec::new(),
+								rename: None,
+								children_elements: 0,
+								has_children_text: false,
+								pending_space: false,
 								output_target_index: frame.output_target_index,
 							});
 						}
@@ -148,27 +363,48 @@ fn process_node_stack_based(
 									.push_str(&s);
 							}
 							None => {
-								output.push_str(&s);
+								global.output.html.push_str(&s);
 							}
 						}
 					}
 					Node::Comment(_) => { /* Skip comments */ }
 					Node::Text(text) => {
+						// Outside the keep scope, text is only kept within <head>
+						if !frame.is_in_keep_scope && !frame.is_in_head_context {
+							continue;
+						}
 						let text_content = text.trim();
 						if !text_content.is_empty() {
-							let s = text.to_string();
-							match frame.output_target_index {
-								Some(idx) => {
-									stack
-										.get_mut(idx)
-										.expect("target frame should exist")
-										.children_output
-										.push_str(&s);
-								}
-								None => {
-									output.push_str(&s);
+							let parent = frame.node.parent().and_then(ElementRef::wrap);
+							let action = parent.map_or(SlimAction::Keep, |parent| {
+								options
+									.rules
+									.action(|rule| match rule.on_text(text, SlimElement::new(parent)) {
+										SlimAction::Unwrap | SlimAction::Rename(_) => SlimAction::Keep,
+										action => action,
+									})
+							});
+							let text = match action {
+								SlimAction::Drop => continue,
+								SlimAction::Rewrite(text) => text,
+								_ => text.to_string(),
+							};
+							let s = match options.max_text_chars.and_then(|max| truncate_text(&text, max)) {
+								Some(truncated) => {
+									stats.truncated_texts += 1;
+									truncated
 								}
-							}
+								None => text,
+							};
+							append_output(
+								&mut stack,
+								&mut global,
+								frame.output_target_index,
+								&s,
+								Vec::new(),
+								0,
+								true,
+							);
 						}
 					}
 					Node::Element(element) => {
@@ -183,9 +419,15 @@ fn process_node_stack_based(
 								stack.push(Frame {
 									node: child,
 									is_in_head_context: child_context_is_in_head,
+									is_in_keep_scope: frame.is_in_keep_scope,
 									depth: frame.depth,
 									state: FrameState::Enter,
 									children_output: String::new(),
+									children_mappings: Vec::new(),
+									rename: None,
+									children_elements: 0,
+									has_children_text: false,
+									pending_space: false,
 									output_target_index: frame.output_target_index,
 								});
 							}
@@ -198,13 +440,11 @@ fn process_node_stack_based(
 						let current_node_is_head = tag_name == "head";
 						let child_context_is_in_head = frame.is_in_head_context || current_node_is_head;
 
-						// Fast-skip rules
 						// Fast-skip rules
 						let should_skip = match tag_name {
-							_ if !child_context_is_in_head && TAGS_TO_REMOVE.contains(&tag_name) => true,
-							"script" | "style" | "link" | "base" | "svg" => true,
+							_ if options.tags_to_remove.contains(tag_name) => true,
 							_ if frame.is_in_head_context => {
-								!(tag_name == "title" || (tag_name == "meta" && should_keep_meta(el_ref)))
+								!(tag_name == "title" || (tag_name == "meta" && should_keep_meta(el_ref, options)))
 							}
 							_ => false,
 						};
@@ -213,14 +453,93 @@ fn process_node_stack_based(
 							continue;
 						}
 
+						// Hidden content and selector rules (body only)
+						let mut child_is_in_keep_scope = frame.is_in_keep_scope;
+						if !child_context_is_in_head {
+							if options.hidden_rules.is_hidden(element) {
+								continue;
+							}
+							let id = frame.node.id();
+							if rules.drop_ids.contains(&id) {
+								stats.dropped_elements += 1;
+								continue;
+							}
+							if options
+								.max_list_items
+								.is_some_and(|max| is_list_item_beyond(frame.node, max))
+							{
+								stats.list_items += 1;
+								continue;
+							}
+							if let (false, Some(keep_scope)) = (frame.is_in_keep_scope, &rules.keep_scope) {
+								if keep_scope.kept_ids.contains(&id) {
+									child_is_in_keep_scope = true;
+								} else if !keep_scope.ancestor_ids.contains(&id) {
+									continue;
+								}
+							}
+						}
+
+						// Custom rules
+						let mut rename = None;
+						match options
+							.rules
+							.action(|rule| rule.on_element_enter(SlimElement::new(el_ref)))
+						{
+							SlimAction::Keep => (),
+							SlimAction::Drop => continue,
+							SlimAction::Rename(name) => rename = Some(name),
+							SlimAction::Rewrite(html) => {
+								let out_mappings = rewrite_mapping(source_map, frame.node, &html).into_iter().collect();
+								append_output(
+									&mut stack,
+									&mut global,
+									frame.output_target_index,
+									&html,
+									out_mappings,
+									1,
+									false,
+								);
+								continue;
+							}
+							SlimAction::Unwrap => {
+								// Same as <html>, the children go to the parent target
+								let mut children: Vec<_> = frame.node.children().collect();
+								children.reverse();
+								for child in children {
+									stack.push(Frame {
+										node: child,
+										is_in_head_context: child_context_is_in_head,
+										is_in_keep_scope: child_is_in_keep_scope,
+										depth: frame.depth,
+										state: FrameState::Enter,
+										children_output: String::new(),
+										children_mappings: Vec::new(),
+										rename: None,
+										children_elements: 0,
+										has_children_text: false,
+										pending_space: false,
+										output_target_index: frame.output_target_index,
+									});
+								}
+								continue;
+							}
+						}
+
 						// Push Exit frame for this element
 						let exit_idx = stack.len();
 						stack.push(Frame {
 							node: frame.node,
 							is_in_head_context: frame.is_in_head_context,
+							is_in_keep_scope: frame.is_in_keep_scope,
 							depth: frame.depth,
 							state: FrameState::Exit,
 							children_output: String::new(),
+							children_mappings: Vec::new(),
+							rename,
+							children_elements: 0,
+							has_children_text: false,
+							pending_space: false,
 							output_target_index: frame.output_target_index,
 						});
 
@@ -239,9 +558,15 @@ fn process_node_stack_based(
 							stack.push(Frame {
 								node: child,
 								is_in_head_context: child_context_is_in_head,
+								is_in_keep_scope: child_is_in_keep_scope,
 								depth: child_depth,
 								state: FrameState::Enter,
 								children_output: String::new(),
+								children_mappings: Vec::new(),
+								rename: None,
+								children_elements: 0,
+								has_children_text: false,
+								pending_space: false,
 								output_target_index: Some(exit_idx),
 							});
 						}
@@ -252,9 +577,15 @@ fn process_node_stack_based(
 							stack.push(Frame {
 								node: child,
 								is_in_head_context: false,
+								is_in_keep_scope: frame.is_in_keep_scope,
 								depth: frame.depth,
 								state: FrameState::Enter,
 								children_output: String::new(),
+								children_mappings: Vec::new(),
+								rename: None,
+								children_elements: 0,
+								has_children_text: false,
+								pending_space: false,
 								output_target_index: frame.output_target_index,
 							});
 						}
@@ -277,7 +608,7 @@ fn process_node_stack_based(
 
 				let is_empty_after_processing = is_string_effectively_empty(&frame.children_output);
 				let is_in_head_for_removal = frame.is_in_head_context || tag_name == "head";
-				let is_removable_tag_when_empty = !is_in_head_for_removal && REMOVABLE_EMPTY_TAGS.contains(&tag_name);
+				let is_removable_tag_when_empty = !is_in_head_for_removal && options.removable_empty_tags.contains(tag_name);
 				let is_empty_head_tag = tag_name == "head" && is_empty_after_processing;
 				let should_remove = (is_removable_tag_when_empty && is_empty_after_processing) || is_empty_head_tag;
 
@@ -285,6 +616,136 @@ fn process_node_stack_based(
 					continue;
 				}
 
+				// Custom rules (the element keeps the formatting of its own tag)
+				let exit_action = options
+					.rules
+					.action(|rule| rule.on_element_exit(SlimElement::new(el_ref), &frame.children_output));
+				let output_tag = match &exit_action {
+					SlimAction::Keep => frame.rename.as_deref().unwrap_or(tag_name),
+					SlimAction::Rename(name) => name.as_str(),
+					SlimAction::Drop => continue,
+					SlimAction::Unwrap => {
+						append_output(
+							&mut stack,
+							&mut global,
+							frame.output_target_index,
+							&frame.children_output,
+							frame.children_mappings,
+							frame.children_elements,
+							frame.has_children_text,
+						);
+						if frame.pending_space {
+							set_pending_space(&mut stack, &mut global, frame.output_target_index);
+						}
+						continue;
+					}
+					SlimAction::Rewrite(html) => {
+						let out_mappings = rewrite_mapping(source_map, frame.node, html).into_iter().collect();
+						append_output(
+							&mut stack,
+							&mut global,
+							frame.output_target_index,
+							html,
+							out_mappings,
+							1,
+							false,
+						);
+						continue;
+					}
+				};
+
+				// Attribute filter uses the head‑context of the element itself
+				let is_in_head_for_attrs = frame.is_in_head_context || tag_name == "head";
+				let mut attrs_out = String::new();
+				let attr_count = filter_and_write_attributes(
+					el_ref,
+					is_in_head_for_attrs,
+					options,
+					rules.base_url.as_deref(),
+					&mut attrs_out,
+				)?;
+
+				// Layout-only containers are replaced by their content (see `SlimOptions::unwrap_tags`)
+				let is_layout_only = attr_count == 0 || (frame.children_elements == 1 && !frame.has_children_text);
+				if !is_in_head_for_removal
+					&& exit_action == SlimAction::Keep
+					&& frame.rename.is_none()
+					&& options.unwrap_tags.contains(tag_name)
+					&& is_layout_only
+				{
+					let mut children_mappings = frame.children_mappings;
+					let mut children_output = frame.children_output;
+					// The children of a block are one level deeper
+					if is_block {
+						let indent_unit = if use_tabs {
+							"\t".to_string()
+						} else {
+							" ".repeat(indent_spaces)
+						};
+						children_output = remove_indent_level(children_output, &indent_unit, &mut children_mappings);
+					}
+
+					// Keep the text of a block container apart from its siblings
+					let is_block_container =
+						BLOCK_LEVEL_TAGS.contains(&tag_name) && !INLINE_FLOW_TAGS.contains(&tag_name);
+					let previous_output = match frame.output_target_index {
+						Some(idx) => stack
+							.get(idx)
+							.map(|target| target.children_output.as_str())
+							.unwrap_or_default(),
+						None => global.output.html.as_str(),
+					};
+					let mut out = String::new();
+					if is_block_container && !children_output.starts_with(|c: char| c == '<' || c.is_whitespace()) {
+						if is_formatting {
+							out.push('\n');
+							let indent_str = if use_tabs {
+								"\t".repeat(frame.depth)
+							} else {
+								" ".repeat(frame.depth * indent_spaces)
+							};
+							out.push_str(&indent_str);
+						} else if !previous_output.is_empty() && !previous_output.ends_with(char::is_whitespace) {
+							out.push(' ');
+						}
+					}
+					let children_offset = out.len();
+					out.push_str(&children_output);
+					// In flat mode, the space is only added when more content follows (see `append_output`)
+					let mut pending_space = frame.pending_space;
+					if is_block_container && !children_output.ends_with(|c: char| c == '>' || c.is_whitespace()) {
+						if is_formatting {
+							out.push('\n');
+							let indent_str = if use_tabs {
+								"\t".repeat(frame.depth)
+							} else {
+								" ".repeat(frame.depth * indent_spaces)
+							};
+							out.push_str(&indent_str);
+						} else {
+							pending_space = true;
+						}
+					}
+
+					let out_mappings = children_mappings
+						.into_iter()
+						.map(|m| m.shifted(children_offset))
+						.collect();
+					append_output(
+						&mut stack,
+						&mut global,
+						frame.output_target_index,
+						&out,
+						out_mappings,
+						frame.children_elements,
+						frame.has_children_text,
+					);
+					if pending_space {
+						set_pending_space(&mut stack, &mut global, frame.output_target_index);
+					}
+					continue;
+				}
+
 				let mut out = String::new();
 
 				// Indent before opening tag (block‑level)
@@ -299,16 +760,37 @@ fn process_node_stack_based(
 				}
 
 				// Start tag with filtered attributes
+				let out_start = out.len();
 				out.push('<');
-				out.push_str(tag_name);
-				// Attribute filter uses the head‑context of the element itself
-				let is_in_head_for_attrs = frame.is_in_head_context || tag_name == "head";
-				filter_and_write_attributes(el_ref, is_in_head_for_attrs, &mut out)?;
+				out.push_str(output_tag);
+				stats.attributes += attr_count;
+				out.push_str(&attrs_out);
 				out.push('>');
 
 				// Append children output
+				let children_offset = out.len();
 				out.push_str(&frame.children_output);
 
+				// Marker of the items removed by `max_list_items` (e.g., `<li>… (3 more)</li>`)
+				let more_items = match options.max_list_items {
+					Some(max) if !is_in_head_for_removal && LIST_TAGS.contains(&tag_name) => {
+						list_items(frame.node).count().saturating_sub(max)
+					}
+					_ => 0,
+				};
+				if more_items > 0 {
+					if is_formatting {
+						out.push('\n');
+						let indent_str = if use_tabs {
+							"\t".repeat(frame.depth + 1)
+						} else {
+							" ".repeat((frame.depth + 1) * indent_spaces)
+						};
+						out.push_str(&indent_str);
+					}
+					out.push_str(&format!("<li>… ({more_items} more)</li>"));
+				}
+
 				// Indent before closing tag if needed
 				if is_block && !is_void && frame.children_output.contains('\n') {
 					out.push('\n');
@@ -323,35 +805,80 @@ fn process_node_stack_based(
 				// Closing tag unless void
 				if !is_void {
 					out.push_str("</");
-					out.push_str(tag_name);
+					out.push_str(output_tag);
 					out.push('>');
 				}
 
-				// Append to parent frame or global output
-				match frame.output_target_index {
-					Some(idx) => {
-						stack
-							.get_mut(idx)
-							.expect("target frame should exist")
-							.children_output
-							.push_str(&out);
-					}
-					None => {
-						output.push_str(&out);
-					}
+				// Mappings of this element (first) and its children (empty without source map)
+				let mut out_mappings = Vec::new();
+				if let Some(span) = source_map.and_then(|source_map| source_map.get(frame.node.id())) {
+					out_mappings.push(SlimMapping {
+						output: out_start..out.len(),
+						source: span,
+					});
 				}
+				out_mappings.extend(frame.children_mappings.into_iter().map(|m| m.shifted(children_offset)));
+
+				// Append to parent frame or global output
+				append_output(
+					&mut stack,
+					&mut global,
+					frame.output_target_index,
+					&out,
+					out_mappings,
+					1,
+					false,
+				);
 			}
 		}
 	}
 
-	Ok(())
+	Ok(stats)
+}
+
+// region:    --- Support
+
+/// The mapping of the HTML replacing the node (from `SlimAction::Rewrite`), when there is a source map.
+fn rewrite_mapping(source_map: Option<&SourceMap>, node: NodeRef<Node>, html: &str) -> Option<SlimMapping> {
+	let span = source_map?.get(node.id())?;
+	Some(SlimMapping {
+		output: 0..html.len(),
+		source: span,
+	})
+}
+
+/// The tags of the lists collapsed by `SlimOptions::max_list_items`.
+const LIST_TAGS: &[&str] = &["ul", "ol", "menu"];
+
+/// The `<li>` children of a list.
+fn list_items<'a>(list: NodeRef<'a, Node>) -> impl Iterator<Item = NodeRef<'a, Node>> {
+	list.children()
+		.filter(|child| child.value().as_element().is_some_and(|el| el.name() == "li"))
 }
 
+/// Whether the node is a list `<li>` with at least `max` `<li>` before it.
+fn is_list_item_beyond(node: NodeRef<Node>, max: usize) -> bool {
+	let is_list_item = node.value().as_element().is_some_and(|el| el.name() == "li")
+		&& node
+			.parent()
+			.and_then(|parent| parent.value().as_element().map(|el| el.name()))
+			.is_some_and(|name| LIST_TAGS.contains(&name));
+	is_list_item
+		&& node
+			.prev_siblings()
+			.filter(|sibling| sibling.value().as_element().is_some_and(|el| el.name() == "li"))
+			.take(max)
+			.count() == max
+}
+
+// endregion: --- Support
+
 // region:    --- Tests
 
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use crate::{HiddenRules, ParseMode};
 	// Result type alias for tests
 	type TestResult<T> = core::result::Result<T, Box<dyn std::error::Error>>;
 
@@ -592,6 +1119,494 @@ mod tests {
 
 		Ok(())
 	}
+
+	#[test]
+	fn test_slimmer2_slim_custom_policies() -> TestResult<()> {
+		// -- Setup & Fixtures
+		let fx_html = r#"
+		<!DOCTYPE html>
+		<html lang="en">
+		<head>
+			<meta name="author" property="article:author" content="Jane">
+			<title>Title</title>
+		</head>
+		<body>
+			<img src="a.png" alt="An image" class="pic">
+			<time datetime="2024-01-01">Jan 1</time>
+			<svg><text>Kept svg</text></svg>
+			<span></span>
+		</body>
+		</html>
+		"#;
+		let options = SlimOptions::default()
+			.extend_allowed_body_attrs(["alt", "src", "datetime"])
+			.subtract_allowed_body_attrs(["class"])
+			.subtract_tags_to_remove(["svg"])
+			.subtract_removable_empty_tags(["span"])
+			.extend_meta_property_keywords(["author"]);
+
+		// -- Exec
+		let html = slim(fx_html, options)?;
+
+		// -- Check
+		assert!(html.contains(r#"<meta content="Jane" property="article:author">"#), "Got: {html}");
+		assert!(html.contains(r#"<img alt="An image" src="a.png">"#), "Got: {html}");
+		assert!(html.contains(r#"<time datetime="2024-01-01">Jan 1</time>"#), "Got: {html}");
+		assert!(html.contains("Kept svg"), "Got: {html}");
+		assert!(html.contains("<span></span>"), "Got: {html}");
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_slimmer2_slim_drop_selectors() -> TestResult<()> {
+		// -- Setup & Fixtures
+		let fx_html = r#"
+		<html>
+		<head><title>Title</title></head>
+		<body>
+			<nav>Menu</nav>
+			<div class="cookie-banner">Accept cookies</div>
+			<div role="dialog">Sign up</div>
+			<p>Content</p>
+			<footer>Footer</footer>
+		</body>
+		</html>
+		"#;
+		let options = SlimOptions::default().with_drop_selectors(["nav", "footer", ".cookie-banner", "[role=dialog]"]);
+
+		// -- Exec
+		let html = slim(fx_html, options)?;
+
+		// -- Check
+		assert!(html.contains("<head><title>Title</title></head>"), "Got: {html}");
+		assert!(html.contains("<body><p>Content</p></body>"), "Got: {html}");
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_slimmer2_slim_keep_selectors() -> TestResult<()> {
+		// -- Setup & Fixtures
+		let fx_html = r#"
+		<html>
+		<head><title>Title</title></head>
+		<body>
+			<header>Header</header>
+			<main>
+				Loose main text
+				<aside>Aside</aside>
+				<article><h1>Article</h1><p>Body <b>text</b></p><nav>Inner nav</nav></article>
+			</main>
+		</body>
+		</html>
+		"#;
+		let options = SlimOptions::default()
+			.with_keep_selectors(["main article"])
+			.with_drop_selectors(["article nav"]);
+
+		// -- Exec
+		let html = slim(fx_html, options)?;
+
+		// -- Check
+		assert!(html.contains("<head><title>Title</title></head>"), "Got: {html}");
+		assert!(
+			html.contains("<body><main><article><h1>Article</h1><p>Body <b>text</b></p></article></main></body>"),
+			"Got: {html}"
+		);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_slimmer2_slim_invalid_selector_rule() -> TestResult<()> {
+		// -- Setup & Fixtures
+		let options = SlimOptions::default().with_drop_selectors(["nav["]);
+
+		// -- Exec
+		let res = slim("<p>Content</p>", options);
+
+		// -- Check
+		assert!(matches!(res, Err(Error::SelectorParse { .. })), "Should be a SelectorParse error");
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_slimmer2_slim_mapped() -> TestResult<()> {
+		// -- Setup & Fixtures
+		let fx_html = r#"<html><body>
+	<div class="card">
+		<script>x()</script>
+		<h2 data-x="1">Title</h2>
+		<p>Some <b>text</b></p>
+	</div>
+</body></html>"#;
+		let options = SlimOptions::default().with_indent(2);
+
+		// -- Exec
+		let output = slim_mapped(fx_html, options.clone())?;
+
+		// -- Check
+		assert_eq!(output.html, slim(fx_html, options)?);
+		let origins: Vec<(&str, &str)> = output
+			.mappings
+			.iter()
+			.map(|m| (&output.html[m.output.clone()], &fx_html[m.source.start..m.source.end]))
+			.collect();
+		assert!(
+			origins.contains(&("<h2>Title</h2>", r#"<h2 data-x="1">Title</h2>"#)),
+			"{origins:?}"
+		);
+		assert!(origins.contains(&("<b>text</b>", "<b>text</b>")), "{origins:?}");
+		let div = output
+			.mappings
+			.iter()
+			.find(|m| m.source.line == 2)
+			.ok_or("Should have the div mapping")?;
+		assert!(output.html[div.output.clone()].starts_with(r#"<div class="card">"#));
+		assert!(output.html[div.output.clone()].ends_with("</div>"));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_slimmer2_slim_fragment() -> TestResult<()> {
+		// -- Setup & Fixtures
+		let fx_html = r#"<p class="intro">Hello <b>there</b></p><script>x()</script><li>Item</li>"#;
+
+		// -- Exec
+		let as_document = slim(fx_html, SlimOptions::default())?;
+		let as_fragment = slim(fx_html, SlimOptions::default().with_parse_mode(ParseMode::fragment()))?;
+		let as_list_items = slim(
+			"<li>A</li><li>B</li>",
+			SlimOptions::default().with_parse_mode(ParseMode::fragment_in("ul")),
+		)?;
+
+		// -- Check
+		assert!(as_document.starts_with("<body>"), "Got: {as_document}");
+		assert_eq!(as_fragment, r#"<p class="intro">Hello <b>there</b></p><li>Item</li>"#);
+		assert_eq!(as_list_items, "<li>A</li><li>B</li>");
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_slimmer2_slim_hidden_rules() -> TestResult<()> {
+		// -- Setup & Fixtures
+		let fx_html = r#"<body>
+	<p>Visible</p>
+	<p hidden>Hidden attr</p>
+	<details><p hidden="until-found">Findable</p></details>
+	<span aria-hidden="true">Icon</span>
+	<div style="color: red; display: none">Display none</div>
+	<div style="visibility:hidden">Visibility hidden</div>
+	<template><p>Template</p></template>
+	<noscript>Enable JS</noscript>
+	<form><input type="hidden" name="token" value="x"><input name="q"></form>
+</body>"#;
+
+		// -- Exec
+		let default_html = slim(fx_html, SlimOptions::default())?;
+		let all_html = slim(fx_html, SlimOptions::default().with_hidden_rules(HiddenRules::all()))?;
+		let aria_only_html = slim(
+			fx_html,
+			SlimOptions::default().with_hidden_rules(HiddenRules::default().with_aria_hidden(true)),
+		)?;
+
+		// -- Check
+		assert!(default_html.contains("Hidden attr") && default_html.contains("Icon"));
+		assert_eq!(
+			all_html,
+			"<body><p>Visible</p><details><p>Findable</p></details><form><input></input></form></body>"
+		);
+		assert!(!aria_only_html.contains("Icon"));
+		assert!(aria_only_html.contains("Hidden attr"));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_slimmer2_slim_max_list_items_and_text_chars() -> TestResult<()> {
+		// -- Setup & Fixtures
+		let fx_html =
+			"<body><ol><li>A</li><li>B</li><li>C</li></ol><p>The quick brown fox jumps over the dog.</p></body>";
+		let fx_options = SlimOptions::default().with_max_list_items(2).with_max_text_chars(20);
+
+		// -- Exec
+		let flat = slim(fx_html, fx_options.clone())?;
+		let indented = slim(fx_html, fx_options.with_indent(2))?;
+
+		// -- Check
+		assert_eq!(
+			flat,
+			"<body><ol><li>A</li><li>B</li><li>… (1 more)</li></ol><p>The quick brown fox…</p></body>"
+		);
+		assert!(
+			indented.contains("    <li>B</li>\n    <li>… (1 more)</li>\n  </ol>"),
+			"{indented}"
+		);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_slimmer2_slim_base_url() -> TestResult<()> {
+		// -- Setup & Fixtures
+		let fx_html = r##"<html><head><base href="/v2/"></head><body>
+	<a href="guide/intro.html">Intro</a>
+	<a href="//cdn.example.com/file.pdf">File</a>
+	<a href="#top">Top</a>
+	<img src="../img/a.png" srcset="a.png 1x, b.png 2x">
+	<form action="search"><input name="q"></form>
+</body></html>"##;
+		let fx_options = SlimOptions::default().extend_allowed_body_attrs(["src", "srcset", "action"]);
+
+		// -- Exec
+		let as_is = slim(fx_html, fx_options.clone())?;
+		let resolved = slim(fx_html, fx_options.with_base_url("https://example.com/docs/page.html"))?;
+
+		// -- Check
+		assert!(as_is.contains(r#"href="guide/intro.html""#));
+		assert!(resolved.contains(r#"href="https://example.com/v2/guide/intro.html""#));
+		assert!(resolved.contains(r#"href="https://cdn.example.com/file.pdf""#));
+		assert!(resolved.contains(r##"href="https://example.com/v2/#top""##));
+		assert!(resolved.contains(r#"src="https://example.com/img/a.png""#));
+		assert!(resolved.contains(r#"srcset="https://example.com/v2/a.png 1x, https://example.com/v2/b.png 2x""#));
+		assert!(resolved.contains(r#"action="https://example.com/v2/search""#));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_slimmer2_slim_rules() -> TestResult<()> {
+		// -- Setup & Fixtures
+		struct FxSiteRule;
+		impl crate::SlimRule for FxSiteRule {
+			fn on_element_enter(&self, element: SlimElement) -> SlimAction {
+				match element.name() {
+					"font" => SlimAction::Unwrap,
+					"b" => SlimAction::Rename("strong".to_string()),
+					"video" => SlimAction::Rewrite("<p>[video]</p>".to_string()),
+					_ if element.has_class("ad") => SlimAction::Drop,
+					_ => SlimAction::Keep,
+				}
+			}
+
+			fn on_element_exit(&self, element: SlimElement, content_html: &str) -> SlimAction {
+				if element.name() == "p" && content_html.len() < 3 {
+					SlimAction::Drop
+				} else {
+					SlimAction::Keep
+				}
+			}
+
+			fn on_text(&self, text: &str, _parent: SlimElement) -> SlimAction {
+				match text.trim() {
+					"Sponsored" => SlimAction::Drop,
+					_ => SlimAction::Rewrite(text.replace("colour", "color")),
+				}
+			}
+
+			fn on_attribute(&self, _element: SlimElement, name: &str, value: &str) -> SlimAction {
+				match name {
+					"class" => SlimAction::Drop,
+					"href" => SlimAction::Rewrite(value.replace("http:", "https:")),
+					"title" => SlimAction::Rename("data-title".to_string()),
+					_ => SlimAction::Keep,
+				}
+			}
+		}
+		let fx_html = r#"<body>
+<div class="ad">Buy now</div>
+<p class="intro"><font>The <b>colour</b> red</font> <a href="http://example.com" title="Home">Home</a></p>
+<p>ok</p>
+<span>Sponsored</span>
+<video src="a.mp4"></video>
+</body>"#;
+
+		// -- Exec
+		let html = slim(fx_html, SlimOptions::default().with_rule(FxSiteRule))?;
+		let mapped = slim_mapped(fx_html, SlimOptions::default().with_rule(FxSiteRule))?;
+
+		// -- Check
+		assert_eq!(
+			html,
+			r#"<body><p>The <strong>color</strong> red<a href="https://example.com" data-title="Home">Home</a></p><p>[video]</p></body>"#
+		);
+		let video = mapped
+			.mappings
+			.iter()
+			.find(|m| &mapped.html[m.output.clone()] == "<p>[video]</p>")
+			.ok_or("Should map the rewritten video")?;
+		assert_eq!(
+			&fx_html[video.source.start..video.source.end],
+			r#"<video src="a.mp4"></video>"#
+		);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_slimmer2_slim_rules_exit_actions() -> TestResult<()> {
+		// -- Setup & Fixtures
+		struct FxExitRule;
+		impl crate::SlimRule for FxExitRule {
+			fn on_element_exit(&self, element: SlimElement, content_html: &str) -> SlimAction {
+				match element.name() {
+					"section" => SlimAction::Unwrap,
+					"b" => SlimAction::Rename("strong".to_string()),
+					"aside" if content_html.contains("<a") => SlimAction::Rewrite("<p>[links]</p>".to_string()),
+					_ => SlimAction::Keep,
+				}
+			}
+		}
+		let fx_html = r#"<body>
+<section><p>One <b>bold</b></p></section>
+<aside><a href="/a">A</a></aside>
+<aside>Note</aside>
+</body>"#;
+
+		// -- Exec
+		let html = slim(fx_html, SlimOptions::default().with_rule(FxExitRule))?;
+
+		// -- Check
+		assert_eq!(
+			html,
+			"<body><p>One <strong>bold</strong></p><p>[links]</p><aside>Note</aside></body>"
+		);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_slimmer2_slim_rules_order() -> TestResult<()> {
+		// -- Setup & Fixtures
+		struct FxFirstRule;
+		impl crate::SlimRule for FxFirstRule {
+			fn on_element_enter(&self, element: SlimElement) -> SlimAction {
+				match element.name() {
+					"i" => SlimAction::Rename("em".to_string()),
+					_ => SlimAction::Keep,
+				}
+			}
+		}
+		struct FxSecondRule;
+		impl crate::SlimRule for FxSecondRule {
+			fn on_element_enter(&self, element: SlimElement) -> SlimAction {
+				match element.name() {
+					"i" => SlimAction::Drop,
+					"b" => SlimAction::Rename("strong".to_string()),
+					_ => SlimAction::Keep,
+				}
+			}
+		}
+		let fx_html = "<body><p><i>a</i> and <b>b</b></p></body>";
+
+		// -- Exec
+		let options = SlimOptions::default().with_rule(FxFirstRule).with_rule(FxSecondRule);
+		let html = slim(fx_html, options)?;
+
+		// -- Check
+		// The first rule renames the `<i>` (the second one is not called), and keeps the `<b>` for the second one
+		assert_eq!(html, "<body><p><em>a</em> and <strong>b</strong></p></body>");
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_slimmer2_slim_rules_meaningless_actions() -> TestResult<()> {
+		// -- Setup & Fixtures
+		struct FxUnwrapRule;
+		impl crate::SlimRule for FxUnwrapRule {
+			fn on_text(&self, _text: &str, _parent: SlimElement) -> SlimAction {
+				SlimAction::Unwrap
+			}
+
+			fn on_attribute(&self, _element: SlimElement, _name: &str, _value: &str) -> SlimAction {
+				SlimAction::Unwrap
+			}
+		}
+		struct FxRewriteRule;
+		impl crate::SlimRule for FxRewriteRule {
+			fn on_text(&self, text: &str, _parent: SlimElement) -> SlimAction {
+				SlimAction::Rewrite(text.to_uppercase())
+			}
+
+			fn on_attribute(&self, _element: SlimElement, name: &str, _value: &str) -> SlimAction {
+				match name {
+					"title" => SlimAction::Drop,
+					_ => SlimAction::Keep,
+				}
+			}
+		}
+		let fx_html = r#"<body><a href="/a" title="A">link</a></body>"#;
+
+		// -- Exec
+		let unwrap_only = slim(fx_html, SlimOptions::default().with_rule(FxUnwrapRule))?;
+		let unwrap_first = slim(
+			fx_html,
+			SlimOptions::default().with_rule(FxUnwrapRule).with_rule(FxRewriteRule),
+		)?;
+
+		// -- Check
+		// `Unwrap` is treated as `Keep` for the texts and attributes, so the next rule applies
+		assert_eq!(unwrap_only, r#"<body><a href="/a" title="A">link</a></body>"#);
+		assert_eq!(unwrap_first, r#"<body><a href="/a">LINK</a></body>"#);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_slimmer2_slim_unwrap_layout() -> TestResult<()> {
+		// -- Setup & Fixtures
+		let fx_html = r#"<body>
+<div class="page"><div><div class="wrapper"><div><p>Hello <span>big</span> world</p></div></div></div></div>
+<div class="card"><h2>Title</h2><p>Text</p></div>
+<div>Alpha</div><div>Beta</div>
+<div>text<div>inner</div>tail</div>
+</body>"#;
+		let fx_options = SlimOptions::default().with_unwrap_layout(true);
+
+		// -- Exec
+		let flat = slim(fx_html, fx_options.clone())?;
+		let indented = slim(fx_html, fx_options.clone().with_indent(2))?;
+		let mapped = slim_mapped(fx_html, fx_options.clone())?;
+		let fragment = slim(
+			"<div>Alpha</div>tail<div>Beta</div>",
+			fx_options.with_parse_mode(ParseMode::fragment()),
+		)?;
+
+		// -- Check
+		assert_eq!(
+			flat,
+			r#"<body><p>Hello big world</p><div class="card"><h2>Title</h2><p>Text</p></div> Alpha Beta text inner tail</body>"#
+		);
+		assert_eq!(fragment, "Alpha tail Beta");
+		assert_eq!(
+			indented,
+			r#"<body>
+  <p>Hello big world</p>
+  <div class="card">
+    <h2>Title</h2>
+    <p>Text</p>
+  </div>
+  Alpha
+  Beta
+  text
+  inner
+  tail
+</body>"#
+		);
+		let h2 = mapped
+			.mappings
+			.iter()
+			.find(|m| &mapped.html[m.output.clone()] == "<h2>Title</h2>")
+			.ok_or("Should map the h2")?;
+		assert_eq!(&fx_html[h2.source.start..h2.source.end], "<h2>Title</h2>");
+
+		Ok(())
+	}
 }
 
 // endregion: --- Tests
@@ -0,0 +1,381 @@
+use super::slim::{SlimStats, slim_pass};
+use super::{SlimOptions, SlimOutput};
+use crate::source::SourceMap;
+use crate::{Document, Result};
+use scraper::Html;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::Arc;
+
+// region:    --- Types
+
+/// Counts the tokens of a text, for `TokenBudget` (e.g., with the tokenizer of the target model).
+///
+/// Implemented for `Fn(&str) -> usize` closures.
+pub trait TokenCounter: Send + Sync {
+	fn count_tokens(&self, text: &str) -> usize;
+}
+
+/// The default `TokenCounter`, a tokenizer-free estimate: about 4 characters per token,
+/// and at least one token per word.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CharTokenEstimator;
+
+/// The maximum number of tokens of the `slim` output (see `SlimOptions::with_token_budget`).
+#[derive(Clone)]
+pub struct TokenBudget {
+	pub max_tokens: usize,
+	/// The token counter (`CharTokenEstimator` by default).
+	pub counter: Arc<dyn TokenCounter>,
+}
+
+/// What `slim` did to fit the token budget (see `slim_with_report`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SlimReport {
+	/// The slimmed HTML (same as `slim`).
+	pub html: String,
+	/// The tokens of `html`.
+	pub tokens: usize,
+	/// The tokens of the output without the budget reductions.
+	pub original_tokens: usize,
+	/// The budget (`None` without `SlimOptions::token_budget`, counted with `CharTokenEstimator`).
+	pub max_tokens: Option<usize>,
+	/// Whether `html` fits the budget. When `false`, all the reductions were applied.
+	pub fits: bool,
+	/// The reductions applied, in order (each one on top of the previous ones).
+	pub reductions: Vec<SlimReduction>,
+}
+
+/// A reduction applied to fit the token budget.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SlimReduction {
+	pub kind: SlimReductionKind,
+	/// The number of attributes, list items, text nodes, or sections cut by this reduction.
+	pub cut: usize,
+	/// The tokens of the output after this reduction.
+	pub tokens_after: usize,
+}
+
+/// The kinds of token budget reductions, from the mildest.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SlimReductionKind {
+	/// The attributes removed from `SlimOptions::allowed_body_attrs`.
+	DropAttributes { names: Vec<String> },
+	/// The lists shortened to their first items (see `SlimOptions::max_list_items`).
+	CollapseLists { max_items: usize },
+	/// The long text nodes truncated (see `SlimOptions::max_text_chars`).
+	TruncateText { max_chars: usize },
+	/// The low-priority sections (navigation, sidebars, footers, forms) added to `SlimOptions::drop_selectors`.
+	RemoveSections { selectors: Vec<String> },
+}
+
+// endregion: --- Types
+
+// region:    --- Constants
+
+/// The sections removed by `SlimReductionKind::RemoveSections`.
+const LOW_PRIORITY_SELECTORS: &[&str] = &[
+	"nav",
+	"aside",
+	"footer",
+	"form",
+	"body > header",
+	"[role=navigation]",
+	"[role=complementary]",
+	"[role=contentinfo]",
+	"[role=search]",
+];
+
+/// The reductions tried in order, until the output fits.
+const REDUCTION_STEPS: &[ReductionStep] = &[
+	ReductionStep::DropAttributes { keep: &["href"] },
+	ReductionStep::CollapseLists(10),
+	ReductionStep::TruncateText(400),
+	ReductionStep::RemoveSections,
+	ReductionStep::CollapseLists(3),
+	ReductionStep::TruncateText(120),
+	ReductionStep::DropAttributes { keep: &[] },
+];
+
+// endregion: --- Constants
+
+// region:    --- Constructors & Impls
+
+impl TokenBudget {
+	/// A budget counted with `CharTokenEstimator`.
+	pub fn new(max_tokens: usize) -> Self {
+		Self {
+			max_tokens,
+			counter: Arc::new(CharTokenEstimator),
+		}
+	}
+
+	/// Set the token counter (e.g., `|text: &str| tokenizer.encode(text).len()`).
+	pub fn with_counter(mut self, counter: impl TokenCounter + 'static) -> Self {
+		self.counter = Arc::new(counter);
+		self
+	}
+}
+
+impl From<usize> for TokenBudget {
+	fn from(max_tokens: usize) -> Self {
+		Self::new(max_tokens)
+	}
+}
+
+impl fmt::Debug for TokenBudget {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("TokenBudget")
+			.field("max_tokens", &self.max_tokens)
+			.finish_non_exhaustive()
+	}
+}
+
+impl TokenCounter for CharTokenEstimator {
+	fn count_tokens(&self, text: &str) -> usize {
+		let chars = text.chars().count();
+		let words = text.split_whitespace().count();
+		chars.div_ceil(4).max(words)
+	}
+}
+
+impl<F> TokenCounter for F
+where
+	F: Fn(&str) -> usize + Send + Sync,
+{
+	fn count_tokens(&self, text: &str) -> usize {
+		self(text)
+	}
+}
+
+// endregion: --- Constructors & Impls
+
+/// Same as `slim`, but also reports the token budget reductions applied (see `SlimOptions::with_token_budget`).
+///
+/// The reductions are tried from the mildest, each on top of the previous ones, until the output fits:
+/// drop the attributes (but `href`), collapse the lists to 10 items, truncate the texts to 400 characters,
+/// remove the low-priority sections (`nav`, `aside`, `footer`, `form`, etc.), collapse the lists to 3 items,
+/// truncate the texts to 120 characters, and drop all the attributes.
+pub fn slim_with_report(html_content: &str, options: impl Into<SlimOptions>) -> Result<SlimReport> {
+	let options = options.into();
+	Document::parse_with_mode(html_content, &options.parse_mode).slim_with_report(options)
+}
+
+/// Slims an already parsed HTML, with the report (see `Document::slim_with_report`).
+pub(crate) fn slim_html_report(html: &Html, options: &SlimOptions) -> Result<SlimReport> {
+	if let Some(budget) = &options.token_budget {
+		return Ok(slim_within_budget(html, options, budget, None)?.1);
+	}
+
+	let (output, _) = slim_pass(html, options, None)?;
+	let tokens = CharTokenEstimator.count_tokens(&output.html);
+	Ok(SlimReport {
+		html: output.html,
+		tokens,
+		original_tokens: tokens,
+		max_tokens: None,
+		fits: true,
+		reductions: Vec::new(),
+	})
+}
+
+/// Slims with the reductions needed to fit the budget (the last output when none fits).
+pub(super) fn slim_within_budget(
+	html: &Html,
+	options: &SlimOptions,
+	budget: &TokenBudget,
+	source_map: Option<&SourceMap>,
+) -> Result<(SlimOutput, SlimReport)> {
+	let (mut output, mut stats) = slim_pass(html, options, source_map)?;
+	let original_tokens = budget.counter.count_tokens(&output.html);
+	let mut tokens = original_tokens;
+	let mut reductions = Vec::new();
+	let mut options = options.clone();
+
+	for step in REDUCTION_STEPS {
+		if tokens <= budget.max_tokens {
+			break;
+		}
+		let Some(kind) = step.apply(&mut options) else {
+			continue;
+		};
+
+		let (step_output, step_stats) = slim_pass(html, &options, source_map)?;
+		tokens = budget.counter.count_tokens(&step_output.html);
+		reductions.push(SlimReduction {
+			cut: kind.cut(&stats, &step_stats),
+			kind,
+			tokens_after: tokens,
+		});
+		(output, stats) = (step_output, step_stats);
+	}
+
+	let report = SlimReport {
+		html: output.html.clone(),
+		tokens,
+		original_tokens,
+		max_tokens: Some(budget.max_tokens),
+		fits: tokens <= budget.max_tokens,
+		reductions,
+	};
+	Ok((output, report))
+}
+
+// region:    --- Reduction Steps
+
+enum ReductionStep {
+	/// Drop the allowed body attributes, but the `keep` ones.
+	DropAttributes {
+		keep: &'static [&'static str],
+	},
+	CollapseLists(usize),
+	TruncateText(usize),
+	RemoveSections,
+}
+
+impl ReductionStep {
+	/// Applies the step to the options, or `None` when the options are already as strict.
+	fn apply(&self, options: &mut SlimOptions) -> Option<SlimReductionKind> {
+		match self {
+			Self::DropAttributes { keep } => {
+				let names: Vec<String> = options
+					.allowed_body_attrs
+					.iter()
+					.filter(|name| !keep.contains(name))
+					.map(str::to_string)
+					.collect();
+				if names.is_empty() {
+					return None;
+				}
+				options.allowed_body_attrs = options.allowed_body_attrs.clone().subtracted(&names);
+				Some(SlimReductionKind::DropAttributes { names })
+			}
+			Self::CollapseLists(max_items) => {
+				if options.max_list_items.is_some_and(|max| max <= *max_items) {
+					return None;
+				}
+				options.max_list_items = Some(*max_items);
+				Some(SlimReductionKind::CollapseLists { max_items: *max_items })
+			}
+			Self::TruncateText(max_chars) => {
+				if options.max_text_chars.is_some_and(|max| max <= *max_chars) {
+					return None;
+				}
+				options.max_text_chars = Some(*max_chars);
+				Some(SlimReductionKind::TruncateText { max_chars: *max_chars })
+			}
+			Self::RemoveSections => {
+				let selectors: Vec<String> = LOW_PRIORITY_SELECTORS
+					.iter()
+					.filter(|selector| !options.drop_selectors.iter().any(|s| s == *selector))
+					.map(|selector| selector.to_string())
+					.collect();
+				if selectors.is_empty() {
+					return None;
+				}
+				options.drop_selectors.extend(selectors.iter().cloned());
+				Some(SlimReductionKind::RemoveSections { selectors })
+			}
+		}
+	}
+}
+
+impl SlimReductionKind {
+	/// The number of items cut, from the stats before and after the reduction.
+	fn cut(&self, before: &SlimStats, after: &SlimStats) -> usize {
+		match self {
+			Self::DropAttributes { .. } => before.attributes.saturating_sub(after.attributes),
+			Self::CollapseLists { .. } => after.list_items.saturating_sub(before.list_items),
+			Self::TruncateText { .. } => after.truncated_texts.saturating_sub(before.truncated_texts),
+			Self::RemoveSections { .. } => after.dropped_elements.saturating_sub(before.dropped_elements),
+		}
+	}
+}
+
+// endregion: --- Reduction Steps
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	type TestResult<T> = core::result::Result<T, Box<dyn std::error::Error>>;
+
+	use super::*;
+	use crate::slim;
+
+	const FX_HTML: &str = r#"<html><body>
+	<nav class="menu"><a href="/">Home</a><a href="/docs">Docs</a></nav>
+	<main class="content" id="main">
+		<h1 class="title">Title</h1>
+		<ul class="items"><li>One</li><li>Two</li><li>Three</li><li>Four</li><li>Five</li><li>Six</li>
+		<li>Seven</li><li>Eight</li><li>Nine</li><li>Ten</li><li>Eleven</li><li>Twelve</li></ul>
+		<p class="text">Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut labore.</p>
+	</main>
+	<footer class="footer">Copyright</footer>
+</body></html>"#;
+
+	#[test]
+	fn test_slim_budget_slim_with_report_within_budget() -> TestResult<()> {
+		// -- Exec
+		let report = slim_with_report(FX_HTML, SlimOptions::default().with_token_budget(1000))?;
+		let no_budget = slim_with_report(FX_HTML, SlimOptions::default())?;
+
+		// -- Check
+		assert!(report.fits);
+		assert!(report.reductions.is_empty());
+		assert_eq!(report.tokens, report.original_tokens);
+		assert_eq!(report.html, no_budget.html);
+		assert_eq!(no_budget.max_tokens, None);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_slim_budget_slim_with_report_reductions() -> TestResult<()> {
+		// -- Setup & Fixtures
+		let fx_counter = |text: &str| text.len() / 4;
+		let fx_budget = TokenBudget::new(80).with_counter(fx_counter);
+
+		// -- Exec
+		let report = slim_with_report(FX_HTML, SlimOptions::default().with_token_budget(fx_budget.clone()))?;
+		let html = slim(FX_HTML, SlimOptions::default().with_token_budget(fx_budget))?;
+
+		// -- Check
+		assert!(report.fits, "{report:#?}");
+		assert!(report.tokens <= 80 && report.original_tokens > 80);
+		assert_eq!(report.html, html);
+		let kinds: Vec<&SlimReductionKind> = report.reductions.iter().map(|r| &r.kind).collect();
+		assert_eq!(
+			kinds,
+			vec![
+				&SlimReductionKind::DropAttributes {
+					names: vec!["aria-label".into(), "class".into(), "id".into(), "title".into()]
+				},
+				&SlimReductionKind::CollapseLists { max_items: 10 },
+				&SlimReductionKind::TruncateText { max_chars: 400 },
+				&SlimReductionKind::RemoveSections {
+					selectors: LOW_PRIORITY_SELECTORS.iter().map(|s| s.to_string()).collect()
+				},
+			]
+		);
+		let cuts: Vec<usize> = report.reductions.iter().map(|r| r.cut).collect();
+		assert_eq!(cuts, vec![7, 2, 0, 2]);
+		assert!(html.contains("<li>Ten</li><li>… (2 more)</li></ul>"), "{html}");
+		assert!(!html.contains("Home") && !html.contains("Copyright"));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_slim_budget_char_token_estimator() -> TestResult<()> {
+		// -- Check
+		assert_eq!(CharTokenEstimator.count_tokens(""), 0);
+		assert_eq!(CharTokenEstimator.count_tokens("abcdefgh"), 2);
+		assert_eq!(CharTokenEstimator.count_tokens("a b c d"), 4);
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
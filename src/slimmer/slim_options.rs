@@ -1,12 +1,85 @@
+use super::support::{
+	ALLOWED_BODY_ATTRS, ALLOWED_META_ATTRS, META_PROPERTY_KEYWORDS, REMOVABLE_EMPTY_TAGS, TAGS_TO_REMOVE,
+	UNWRAP_LAYOUT_TAGS,
+};
+use super::{HiddenRules, NameSet, SlimRule, SlimRules, TokenBudget};
+use crate::ParseMode;
+
 // region:    --- Types
 
-/// Options for the `slim` function (indentation, etc.).
-#[derive(Clone, Copy, Debug, Default)]
+/// Options for the `slim` function (indentation, tag/attribute policies, etc.).
+///
+/// All the policy sets default to the crate built-in lists, and can be replaced (`with_...`),
+/// extended (`extend_...`), or subtracted from (`subtract_...`).
+#[derive(Clone, Debug)]
 pub struct SlimOptions {
 	/// Whether to use tabs instead of spaces for indentation.
 	pub indent_with_tabs: bool,
 	/// Number of spaces per indentation level, or `None` for flat output.
 	pub indent: Option<u8>,
+
+	/// Tags removed with all their content (e.g., `script`, `style`).
+	pub tags_to_remove: NameSet,
+	/// Tags removed when they are effectively empty after processing (outside `<head>`).
+	pub removable_empty_tags: NameSet,
+	/// Tags replaced by their content (outside `<head>`) when they have no kept attribute, or when their content
+	/// is a single element (none by default, see `with_unwrap_layout`).
+	pub unwrap_tags: NameSet,
+	/// Attributes kept on elements outside `<head>`.
+	pub allowed_body_attrs: NameSet,
+	/// Attributes kept on the `<meta>` tags kept in `<head>`.
+	pub allowed_meta_attrs: NameSet,
+	/// A `<meta>` is kept when its `property` contains one of these keywords (or its `name`, when `name`
+	/// is one of the `allowed_meta_attrs`).
+	pub meta_property_keywords: NameSet,
+
+	/// CSS selectors of body elements to drop with all their content (e.g., `nav`, `.cookie-banner`).
+	pub drop_selectors: Vec<String>,
+	/// CSS selectors scoping the body output. When not empty, only the matching elements (with their content)
+	/// and the ancestors leading to them are kept.
+	pub keep_selectors: Vec<String>,
+	/// The rules removing the content never visible to users (all disabled by default).
+	pub hidden_rules: HiddenRules,
+	/// The absolute base URL the kept `href`, `src`, `srcset`, and `action` attributes are resolved against
+	/// (`None` to keep them as is). A `<base href>` of the document takes precedence (resolved against it).
+	pub base_url: Option<String>,
+
+	/// The custom rules layered on top of the built-in ones (see `SlimRule`).
+	pub rules: SlimRules,
+
+	/// The maximum number of `<li>` kept per list, followed by a `<li>… (n more)</li>` marker (`None` for all).
+	pub max_list_items: Option<usize>,
+	/// The maximum number of characters of a text node, truncated with a `…` suffix (`None` for no limit).
+	pub max_text_chars: Option<usize>,
+	/// The maximum number of tokens of the output, reached with progressive reductions (see `slim_with_report`).
+	pub token_budget: Option<TokenBudget>,
+
+	/// How `slim` parses the HTML (full document by default). Not used by `Document::slim` (already parsed).
+	pub parse_mode: ParseMode,
+}
+
+impl Default for SlimOptions {
+	fn default() -> Self {
+		Self {
+			indent_with_tabs: false,
+			indent: None,
+			tags_to_remove: NameSet::from_static(TAGS_TO_REMOVE),
+			removable_empty_tags: NameSet::from_static(REMOVABLE_EMPTY_TAGS),
+			unwrap_tags: NameSet::default(),
+			allowed_body_attrs: NameSet::from_static(ALLOWED_BODY_ATTRS),
+			allowed_meta_attrs: NameSet::from_static(ALLOWED_META_ATTRS),
+			meta_property_keywords: NameSet::from_static(META_PROPERTY_KEYWORDS),
+			drop_selectors: Vec::new(),
+			keep_selectors: Vec::new(),
+			hidden_rules: HiddenRules::default(),
+			base_url: None,
+			rules: SlimRules::default(),
+			max_list_items: None,
+			max_text_chars: None,
+			token_budget: None,
+			parse_mode: ParseMode::Document,
+		}
+	}
 }
 
 // endregion: --- Types
@@ -25,10 +98,188 @@ impl SlimOptions {
 		self.indent_with_tabs = tabs;
 		self
 	}
+
+	/// Unwrap the layout-only `div`/`span` wrappers (see `unwrap_tags`), e.g., ten nested single-child `<div>`s.
+	pub fn with_unwrap_layout(mut self, unwrap: bool) -> Self {
+		self.unwrap_tags = if unwrap {
+			NameSet::from_static(UNWRAP_LAYOUT_TAGS)
+		} else {
+			NameSet::default()
+		};
+		self
+	}
+
+	/// Set the CSS selectors of the elements to drop (e.g., `["nav", "footer", "[role=dialog]"]`).
+	pub fn with_drop_selectors(mut self, selectors: impl IntoIterator<Item = impl Into<String>>) -> Self {
+		self.drop_selectors = selectors.into_iter().map(Into::into).collect();
+		self
+	}
+
+	/// Set the CSS selectors scoping the output (e.g., `["main article"]`).
+	pub fn with_keep_selectors(mut self, selectors: impl IntoIterator<Item = impl Into<String>>) -> Self {
+		self.keep_selectors = selectors.into_iter().map(Into::into).collect();
+		self
+	}
+
+	/// Set the rules removing the hidden content (e.g., `HiddenRules::all()`).
+	pub fn with_hidden_rules(mut self, hidden_rules: HiddenRules) -> Self {
+		self.hidden_rules = hidden_rules;
+		self
+	}
+
+	/// Set the base URL to resolve the relative URL attributes against (e.g., the page URL).
+	pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+		self.base_url = Some(base_url.into());
+		self
+	}
+
+	/// Add a custom rule, run after the ones already added (e.g., to drop or rename site-specific elements).
+	pub fn with_rule(mut self, rule: impl SlimRule + 'static) -> Self {
+		self.rules = self.rules.with(rule);
+		self
+	}
+
+	/// Keep at most `max_items` `<li>` per list (`<ul>`, `<ol>`, `<menu>`).
+	pub fn with_max_list_items(mut self, max_items: usize) -> Self {
+		self.max_list_items = Some(max_items);
+		self
+	}
+
+	/// Truncate the text nodes longer than `max_chars` characters (at a word boundary when possible).
+	pub fn with_max_text_chars(mut self, max_chars: usize) -> Self {
+		self.max_text_chars = Some(max_chars);
+		self
+	}
+
+	/// Set the token budget of the output (e.g., `2000`, or `TokenBudget::new(2000).with_counter(..)`).
+	pub fn with_token_budget(mut self, budget: impl Into<TokenBudget>) -> Self {
+		self.token_budget = Some(budget.into());
+		self
+	}
+
+	/// Set how `slim` parses the HTML (e.g., `ParseMode::fragment()` for snippets, without `<html>`/`<body>` output).
+	pub fn with_parse_mode(mut self, parse_mode: ParseMode) -> Self {
+		self.parse_mode = parse_mode;
+		self
+	}
 }
 
 // endregion: --- Constructors & Fluid API
 
+// region:    --- Policy Fluid API
+
+impl SlimOptions {
+	/// Replace the tags removed with all their content.
+	pub fn with_tags_to_remove(mut self, names: impl IntoIterator<Item = impl AsRef<str>>) -> Self {
+		self.tags_to_remove = NameSet::new(names);
+		self
+	}
+
+	/// Add tags to be removed with all their content.
+	pub fn extend_tags_to_remove(mut self, names: impl IntoIterator<Item = impl AsRef<str>>) -> Self {
+		self.tags_to_remove = self.tags_to_remove.extended(names);
+		self
+	}
+
+	/// Stop removing the given tags (they will be processed like any other element).
+	pub fn subtract_tags_to_remove(mut self, names: impl IntoIterator<Item = impl AsRef<str>>) -> Self {
+		self.tags_to_remove = self.tags_to_remove.subtracted(names);
+		self
+	}
+
+	/// Replace the tags removed when effectively empty.
+	pub fn with_removable_empty_tags(mut self, names: impl IntoIterator<Item = impl AsRef<str>>) -> Self {
+		self.removable_empty_tags = NameSet::new(names);
+		self
+	}
+
+	/// Add tags to be removed when effectively empty.
+	pub fn extend_removable_empty_tags(mut self, names: impl IntoIterator<Item = impl AsRef<str>>) -> Self {
+		self.removable_empty_tags = self.removable_empty_tags.extended(names);
+		self
+	}
+
+	/// Keep the given tags even when effectively empty.
+	pub fn subtract_removable_empty_tags(mut self, names: impl IntoIterator<Item = impl AsRef<str>>) -> Self {
+		self.removable_empty_tags = self.removable_empty_tags.subtracted(names);
+		self
+	}
+
+	/// Replace the tags unwrapped when layout-only.
+	pub fn with_unwrap_tags(mut self, names: impl IntoIterator<Item = impl AsRef<str>>) -> Self {
+		self.unwrap_tags = NameSet::new(names);
+		self
+	}
+
+	/// Add tags to unwrap when layout-only (e.g., `font`, `center`).
+	pub fn extend_unwrap_tags(mut self, names: impl IntoIterator<Item = impl AsRef<str>>) -> Self {
+		self.unwrap_tags = self.unwrap_tags.extended(names);
+		self
+	}
+
+	/// Stop unwrapping the given tags.
+	pub fn subtract_unwrap_tags(mut self, names: impl IntoIterator<Item = impl AsRef<str>>) -> Self {
+		self.unwrap_tags = self.unwrap_tags.subtracted(names);
+		self
+	}
+
+	/// Replace the attributes kept outside `<head>`.
+	pub fn with_allowed_body_attrs(mut self, names: impl IntoIterator<Item = impl AsRef<str>>) -> Self {
+		self.allowed_body_attrs = NameSet::new(names);
+		self
+	}
+
+	/// Add attributes to keep outside `<head>` (e.g., `alt`, `src`, `datetime`, `lang`).
+	pub fn extend_allowed_body_attrs(mut self, names: impl IntoIterator<Item = impl AsRef<str>>) -> Self {
+		self.allowed_body_attrs = self.allowed_body_attrs.extended(names);
+		self
+	}
+
+	/// Drop the given attributes outside `<head>`.
+	pub fn subtract_allowed_body_attrs(mut self, names: impl IntoIterator<Item = impl AsRef<str>>) -> Self {
+		self.allowed_body_attrs = self.allowed_body_attrs.subtracted(names);
+		self
+	}
+
+	/// Replace the attributes kept on `<meta>` tags.
+	pub fn with_allowed_meta_attrs(mut self, names: impl IntoIterator<Item = impl AsRef<str>>) -> Self {
+		self.allowed_meta_attrs = NameSet::new(names);
+		self
+	}
+
+	/// Add attributes to keep on `<meta>` tags.
+	pub fn extend_allowed_meta_attrs(mut self, names: impl IntoIterator<Item = impl AsRef<str>>) -> Self {
+		self.allowed_meta_attrs = self.allowed_meta_attrs.extended(names);
+		self
+	}
+
+	/// Drop the given attributes on `<meta>` tags.
+	pub fn subtract_allowed_meta_attrs(mut self, names: impl IntoIterator<Item = impl AsRef<str>>) -> Self {
+		self.allowed_meta_attrs = self.allowed_meta_attrs.subtracted(names);
+		self
+	}
+
+	/// Replace the keywords matched against the `<meta property>` attribute.
+	pub fn with_meta_property_keywords(mut self, names: impl IntoIterator<Item = impl AsRef<str>>) -> Self {
+		self.meta_property_keywords = NameSet::new(names);
+		self
+	}
+
+	/// Add keywords matched against the `<meta property>` attribute.
+	pub fn extend_meta_property_keywords(mut self, names: impl IntoIterator<Item = impl AsRef<str>>) -> Self {
+		self.meta_property_keywords = self.meta_property_keywords.extended(names);
+		self
+	}
+
+	/// Remove keywords matched against the `<meta property>` attribute.
+	pub fn subtract_meta_property_keywords(mut self, names: impl IntoIterator<Item = impl AsRef<str>>) -> Self {
+		self.meta_property_keywords = self.meta_property_keywords.subtracted(names);
+		self
+	}
+}
+
+// endregion: --- Policy Fluid API
+
 // region:    --- Tests
 
 #[cfg(test)]
@@ -46,6 +297,9 @@ mod tests {
 		// -- Check
 		assert!(!opts.indent_with_tabs, "indent_with_tabs should default to false");
 		assert!(opts.indent.is_none(), "indent should default to None");
+		assert!(opts.tags_to_remove.contains("script"));
+		assert!(opts.allowed_body_attrs.contains("href"));
+		assert!(!opts.allowed_body_attrs.contains("alt"));
 
 		Ok(())
 	}
@@ -88,6 +342,27 @@ mod tests {
 
 		Ok(())
 	}
+
+	#[test]
+	fn test_slim_options_policy_edits() -> TestResult<()> {
+		// -- Setup & Fixtures
+		// -- Exec
+		let opts = SlimOptions::default()
+			.extend_allowed_body_attrs(["alt", "src"])
+			.subtract_allowed_body_attrs(["class"])
+			.subtract_tags_to_remove(["svg"])
+			.with_meta_property_keywords(["author"]);
+
+		// -- Check
+		assert!(opts.allowed_body_attrs.contains("alt"));
+		assert!(opts.allowed_body_attrs.contains("src"));
+		assert!(!opts.allowed_body_attrs.contains("class"));
+		assert!(!opts.tags_to_remove.contains("svg"));
+		assert!(opts.tags_to_remove.contains("script"));
+		assert_eq!(opts.meta_property_keywords.iter().collect::<Vec<_>>(), vec!["author"]);
+
+		Ok(())
+	}
 }
 
 // endregion: --- Tests
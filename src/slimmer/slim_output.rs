@@ -0,0 +1,32 @@
+use crate::SourceSpan;
+use serde::{Deserialize, Serialize};
+use std::ops::Range;
+
+/// The result of `slim_mapped`: the slimmed HTML, with the source location of its elements.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SlimOutput {
+	/// The slimmed HTML (same as `slim`).
+	pub html: String,
+	/// One mapping per output element with a known source location, ordered by output start
+	/// (parents before their children). Text maps to the innermost enclosing element.
+	pub mappings: Vec<SlimMapping>,
+}
+
+/// Maps an element of the slimmed HTML back to the HTML source.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SlimMapping {
+	/// The byte range of the element in `SlimOutput::html` (start tag to end tag).
+	pub output: Range<usize>,
+	/// The element location in the HTML source.
+	pub source: SourceSpan,
+}
+
+impl SlimMapping {
+	/// Moves the output range by `offset`.
+	pub(super) fn shifted(self, offset: usize) -> Self {
+		Self {
+			output: self.output.start + offset..self.output.end + offset,
+			source: self.source,
+		}
+	}
+}
@@ -0,0 +1,162 @@
+use super::SlimOptions;
+
+// region:    --- Types
+
+/// Named, tested combinations of `SlimOptions` policies.
+///
+/// The exact rules of each preset are part of the crate contract and are covered by
+/// golden-file tests (`tests-data/slim-presets/`), so upgrades do not silently change the output.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SlimPreset {
+	/// Compact, semantic markup for LLM prompts (flat output).
+	///
+	/// - Removes: defaults + `noscript`, `iframe`, `canvas`, `video`, `audio`, `object`, `embed`, `template`.
+	/// - Removes when empty: defaults + `li`, `ul`, `ol`, `a`.
+	/// - Body attributes: `href`, `title`, `alt`, `aria-label` (no `class`/`id`).
+	/// - Head: default `<meta>` policy.
+	LlmContext,
+
+	/// Faithful copy of the content, keeping media and dates (indent of 2).
+	///
+	/// - Removes: `script`, `style`, `link`, `base` (keeps `svg`).
+	/// - Removes when empty: `div`, `span`.
+	/// - Body attributes: defaults + `alt`, `src`, `srcset`, `datetime`, `lang`, `cite`, `colspan`, `rowspan`, `rel`.
+	/// - Head: meta attributes defaults + `name` (so `name` keyed metas are kept too); meta keywords defaults + `author`, `published_time`,
+	///   `modified_time`, `site_name`, `type`, `locale`.
+	Archive,
+
+	/// Reading view, dropping site chrome (flat output).
+	///
+	/// - Removes: defaults + `nav`, `header`, `footer`, `aside`, `form`, `button`, `noscript`, `iframe`,
+	///   `template`, `dialog`.
+	/// - Removes when empty: defaults + `li`, `a`, `figure`.
+	/// - Body attributes: `href`, `src`, `alt`, `title`.
+	/// - Head: default `<meta>` policy.
+	Reader,
+
+	/// Bare text structure, no attributes and no media (flat output).
+	///
+	/// - Removes: defaults + `noscript`, `iframe`, `canvas`, `video`, `audio`, `object`, `embed`, `template`,
+	///   `img`, `picture`, `form`, `button`, `input`, `select`, `textarea`.
+	/// - Removes when empty: defaults + `li`, `a`.
+	/// - Body attributes: none.
+	/// - Head: `<title>` only (no `<meta>` is kept).
+	TextOnly,
+}
+
+// endregion: --- Types
+
+// region:    --- Constructors
+
+impl SlimOptions {
+	/// Creates the `SlimOptions` for a named preset.
+	///
+	/// The result can be further customized with the `with_...`, `extend_...`, and `subtract_...` methods.
+	pub fn preset(preset: SlimPreset) -> Self {
+		let base = SlimOptions::default();
+		match preset {
+			SlimPreset::LlmContext => base
+				.extend_tags_to_remove(["noscript", "iframe", "canvas", "video", "audio", "object", "embed", "template"])
+				.extend_removable_empty_tags(["li", "ul", "ol", "a"])
+				.with_allowed_body_attrs(["href", "title", "alt", "aria-label"]),
+
+			SlimPreset::Archive => base
+				.with_indent(2)
+				.subtract_tags_to_remove(["svg"])
+				.with_removable_empty_tags(["div", "span"])
+				.extend_allowed_body_attrs([
+					"alt", "src", "srcset", "datetime", "lang", "cite", "colspan", "rowspan", "rel",
+				])
+				.extend_allowed_meta_attrs(["name"])
+				.extend_meta_property_keywords([
+					"author",
+					"published_time",
+					"modified_time",
+					"site_name",
+					"type",
+					"locale",
+				]),
+
+			SlimPreset::Reader => base
+				.extend_tags_to_remove([
+					"nav", "header", "footer", "aside", "form", "button", "noscript", "iframe", "template", "dialog",
+				])
+				.extend_removable_empty_tags(["li", "a", "figure"])
+				.with_allowed_body_attrs(["href", "src", "alt", "title"]),
+
+			SlimPreset::TextOnly => base
+				.extend_tags_to_remove([
+					"noscript", "iframe", "canvas", "video", "audio", "object", "embed", "template", "img", "picture",
+					"form", "button", "input", "select", "textarea",
+				])
+				.extend_removable_empty_tags(["li", "a"])
+				.with_allowed_body_attrs(Vec::<&str>::new())
+				.with_allowed_meta_attrs(Vec::<&str>::new())
+				.with_meta_property_keywords(Vec::<&str>::new()),
+		}
+	}
+}
+
+impl From<SlimPreset> for SlimOptions {
+	fn from(preset: SlimPreset) -> Self {
+		SlimOptions::preset(preset)
+	}
+}
+
+// endregion: --- Constructors
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	type TestResult<T> = core::result::Result<T, Box<dyn std::error::Error>>;
+
+	use super::*;
+	use crate::slim;
+
+	const FX_PAGE: &str = include_str!("../../tests-data/slim-presets/page.html");
+
+	fn assert_golden(preset: SlimPreset, expected: &str) -> TestResult<()> {
+		let html = slim(FX_PAGE, preset)?;
+		assert_eq!(
+			html,
+			expected.trim_end_matches('\n'),
+			"Preset {preset:?} output changed. Got:\n{html}"
+		);
+		Ok(())
+	}
+
+	#[test]
+	fn test_slim_preset_llm_context_golden() -> TestResult<()> {
+		assert_golden(
+			SlimPreset::LlmContext,
+			include_str!("../../tests-data/slim-presets/expected-llm-context.html"),
+		)
+	}
+
+	#[test]
+	fn test_slim_preset_archive_golden() -> TestResult<()> {
+		assert_golden(
+			SlimPreset::Archive,
+			include_str!("../../tests-data/slim-presets/expected-archive.html"),
+		)
+	}
+
+	#[test]
+	fn test_slim_preset_reader_golden() -> TestResult<()> {
+		assert_golden(
+			SlimPreset::Reader,
+			include_str!("../../tests-data/slim-presets/expected-reader.html"),
+		)
+	}
+
+	#[test]
+	fn test_slim_preset_text_only_golden() -> TestResult<()> {
+		assert_golden(
+			SlimPreset::TextOnly,
+			include_str!("../../tests-data/slim-presets/expected-text-only.html"),
+		)
+	}
+}
+
+// endregion: --- Tests
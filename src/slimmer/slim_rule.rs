@@ -0,0 +1,154 @@
+use scraper::ElementRef;
+use std::fmt;
+use std::sync::Arc;
+
+// region:    --- Types
+
+/// A custom rule layered on top of the built-in `slim` pipeline (see `SlimOptions::with_rule`).
+///
+/// The hooks are only called on what the built-in rules keep (e.g., not on `<script>` or dropped elements),
+/// and all default to `SlimAction::Keep`.
+pub trait SlimRule: Send + Sync {
+	/// Called when entering an element, before its children.
+	///
+	/// - `Drop` removes the element with its content.
+	/// - `Unwrap` outputs the content without the element tags.
+	/// - `Rename` outputs the element with another tag (same attributes).
+	/// - `Rewrite` outputs the given HTML instead of the element and its content.
+	fn on_element_enter(&self, _element: SlimElement) -> SlimAction {
+		SlimAction::Keep
+	}
+
+	/// Called when exiting an element, with the slimmed HTML of its content (same actions as `on_element_enter`,
+	/// e.g., `Drop` when the content is too short).
+	fn on_element_exit(&self, _element: SlimElement, _content_html: &str) -> SlimAction {
+		SlimAction::Keep
+	}
+
+	/// Called on each non-empty text node. `Drop` removes the text, `Rewrite` replaces it (written as is).
+	/// `Unwrap` and `Rename` are meaningless for a text, and treated as `Keep` (the next rules still run).
+	fn on_text(&self, _text: &str, _parent: SlimElement) -> SlimAction {
+		SlimAction::Keep
+	}
+
+	/// Called on each attribute kept by the allowlists (with its resolved URL, see `SlimOptions::base_url`).
+	/// `Drop` removes the attribute, `Rename` changes its name, and `Rewrite` replaces its value.
+	/// `Unwrap` is meaningless for an attribute, and treated as `Keep` (the next rules still run).
+	fn on_attribute(&self, _element: SlimElement, _name: &str, _value: &str) -> SlimAction {
+		SlimAction::Keep
+	}
+}
+
+/// What a `SlimRule` hook does to a node (see the hooks for what each action means for their node).
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum SlimAction {
+	/// Continue with the built-in pipeline (or the next rule).
+	#[default]
+	Keep,
+	/// Remove the node.
+	Drop,
+	/// Output the element content without its tags (elements only).
+	Unwrap,
+	/// Output the element (or attribute) with another name.
+	Rename(String),
+	/// Output the given HTML (or text, attribute value) instead.
+	Rewrite(String),
+}
+
+/// The element a `SlimRule` hook is called on (a read-only view of the source element).
+#[derive(Clone, Copy)]
+pub struct SlimElement<'a> {
+	el: ElementRef<'a>,
+}
+
+/// The rules of `SlimOptions::rules`, run in order. For each hook, the first action other than `Keep` wins.
+#[derive(Clone, Default)]
+pub struct SlimRules {
+	rules: Vec<Arc<dyn SlimRule>>,
+}
+
+// endregion: --- Types
+
+// region:    --- SlimElement
+
+impl<'a> SlimElement<'a> {
+	pub(super) fn new(el: ElementRef<'a>) -> Self {
+		Self { el }
+	}
+
+	/// The lowercase tag name (e.g., `div`).
+	pub fn name(&self) -> &'a str {
+		self.el.value().name()
+	}
+
+	/// The value of the attribute (as in the source, before any slim filtering).
+	pub fn attr(&self, name: &str) -> Option<&'a str> {
+		self.el.value().attr(name)
+	}
+
+	/// The attributes, in source order.
+	pub fn attrs(&self) -> impl Iterator<Item = (&'a str, &'a str)> {
+		self.el.value().attrs()
+	}
+
+	pub fn id(&self) -> Option<&'a str> {
+		self.el.value().id()
+	}
+
+	pub fn has_class(&self, class: &str) -> bool {
+		self.el.value().classes().any(|c| c == class)
+	}
+
+	/// The source text of the element (with the text of the nodes removed by slim).
+	pub fn text(&self) -> String {
+		self.el.text().collect()
+	}
+
+	/// The parent element (`None` for the root).
+	pub fn parent(&self) -> Option<SlimElement<'a>> {
+		self.el.parent().and_then(ElementRef::wrap).map(Self::new)
+	}
+}
+
+impl fmt::Debug for SlimElement<'_> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("SlimElement").field("name", &self.name()).finish()
+	}
+}
+
+// endregion: --- SlimElement
+
+// region:    --- SlimRules
+
+impl SlimRules {
+	/// Returns these rules with the rule added last.
+	pub fn with(mut self, rule: impl SlimRule + 'static) -> Self {
+		self.rules.push(Arc::new(rule));
+		self
+	}
+
+	pub fn len(&self) -> usize {
+		self.rules.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.rules.is_empty()
+	}
+
+	/// The first action other than `Keep` of the rules (or `Keep`).
+	pub(super) fn action(&self, hook: impl Fn(&dyn SlimRule) -> SlimAction) -> SlimAction {
+		self.rules
+			.iter()
+			.map(|rule| hook(rule.as_ref()))
+			.find(|action| *action != SlimAction::Keep)
+			.unwrap_or_default()
+	}
+}
+
+impl fmt::Debug for SlimRules {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("SlimRules").field("len", &self.rules.len()).finish()
+	}
+}
+
+// endregion: --- SlimRules
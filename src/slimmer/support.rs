@@ -1,48 +1,102 @@
+use super::{NameSet, SlimAction, SlimElement, SlimMapping, SlimOptions};
 use crate::Result;
+use crate::url::resolve_url_attr;
 use html_escape::encode_double_quoted_attribute;
 use scraper::ElementRef;
 
 // region:    --- Constants
 
-/// Tags to remove explicitly, regardless of content (unless within <head>).
+/// Default tags to remove explicitly, regardless of content.
 pub(super) const TAGS_TO_REMOVE: &[&str] = &["script", "link", "style", "svg", "base"];
 
-/// Tags that should be removed if they become effectively empty (contain only whitespace/comments)
+/// Default tags that should be removed if they become effectively empty (contain only whitespace/comments)
 /// after processing children. Applies only outside the <head> element.
 pub(super) const REMOVABLE_EMPTY_TAGS: &[&str] =
 	&["div", "span", "p", "i", "b", "em", "strong", "section", "article", "header", "footer", "nav", "aside"];
 
-/// Keywords to check within the 'property' attribute of <meta> tags to determine if they should be kept.
+/// The layout-only containers unwrapped by `SlimOptions::with_unwrap_layout`.
+pub(super) const UNWRAP_LAYOUT_TAGS: &[&str] = &["div", "span"];
+
+/// Default keywords to check within the 'property' attribute of <meta> tags to determine if they should be kept.
 pub(super) const META_PROPERTY_KEYWORDS: &[&str] = &["title", "url", "image", "description"];
 
-/// Attribute names allowed on <meta> tags within the <head>.
+/// Default attribute names allowed on <meta> tags within the <head>.
 pub(super) const ALLOWED_META_ATTRS: &[&str] = &["property", "content"];
 
-/// Attribute names allowed on elements outside the <head>.
+/// Default attribute names allowed on elements outside the <head>.
 pub(super) const ALLOWED_BODY_ATTRS: &[&str] = &["class", "aria-label", "href", "title", "id"];
 
-/// Tags considered block-level for indentation purposes (used when indent > 0).
-#[rustfmt::skip]
-pub(super) const BLOCK_LEVEL_TAGS: &[&str] = &[
-	"html", "head", "body", "a", "div", "p", "section", "article", "header", "footer", "nav", "aside",
-	"ul", "ol", "li", "table", "tr", "td", "th", "h1", "h2", "h3", "h4", "h5", "h6",
-	"pre", "blockquote", "main", "form", "fieldset", "details", "summary", "figure", "figcaption",
-	"dl", "dt", "dd", "br", "hr", "img", "input", "meta", "link", "script", "style",
-];
-
-/// HTML void elements (should not have closing tags).
-#[rustfmt::skip]
-pub(super) const VOID_ELEMENTS: &[&str] = &[
-	"area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta",
-	"param", "source", "track", "wbr",
-];
-
 // endregion: --- Constants
 
 /// Removes empty lines from the given content, returning the cleaned string.
-pub(super) fn remove_empty_lines(content: String) -> Result<String> {
-	let lines: Vec<&str> = content.lines().filter(|line| !line.trim().is_empty()).collect();
-	Ok(lines.join("\n"))
+///
+/// The output ranges of the `mappings` are moved accordingly.
+pub(super) fn remove_empty_lines(content: String, mappings: &mut [SlimMapping]) -> Result<String> {
+	// (content offset, result offset, length) of the kept lines
+	let mut kept_lines: Vec<(usize, usize, usize)> = Vec::new();
+	let mut result = String::with_capacity(content.len());
+	let mut line_start = 0;
+
+	// Same line splitting as `str::lines`
+	for raw_line in content.split_inclusive('\n') {
+		let line = match raw_line.strip_suffix('\n') {
+			Some(line) => line.strip_suffix('\r').unwrap_or(line),
+			None => raw_line,
+		};
+		if !line.trim().is_empty() {
+			if !kept_lines.is_empty() {
+				result.push('\n');
+			}
+			kept_lines.push((line_start, result.len(), line.len()));
+			result.push_str(line);
+		}
+		line_start += raw_line.len();
+	}
+
+	let remap = |offset: usize| match kept_lines.partition_point(|(start, _, _)| *start <= offset) {
+		0 => 0,
+		idx => {
+			let (start, new_start, len) = kept_lines[idx - 1];
+			new_start + (offset - start).min(len)
+		}
+	};
+	for mapping in mappings.iter_mut() {
+		mapping.output = remap(mapping.output.start)..remap(mapping.output.end);
+	}
+
+	Ok(result)
+}
+
+/// Removes one indentation `unit` at the start of each line (after a `\n`), moving the `mappings` accordingly.
+pub(super) fn remove_indent_level(content: String, unit: &str, mappings: &mut [SlimMapping]) -> String {
+	if unit.is_empty() {
+		return content;
+	}
+
+	// The content offsets of the removed units
+	let mut removed: Vec<usize> = Vec::new();
+	let mut result = String::with_capacity(content.len());
+	let mut line_start = 0;
+	for line in content.split_inclusive('\n') {
+		match line.strip_prefix(unit) {
+			Some(rest) if line_start > 0 => {
+				removed.push(line_start);
+				result.push_str(rest);
+			}
+			_ => result.push_str(line),
+		}
+		line_start += line.len();
+	}
+
+	let remap = |offset: usize| match removed.partition_point(|start| *start < offset) {
+		0 => offset,
+		idx => offset - (idx - 1) * unit.len() - (offset - removed[idx - 1]).min(unit.len()),
+	};
+	for mapping in mappings.iter_mut() {
+		mapping.output = remap(mapping.output.start)..remap(mapping.output.end);
+	}
+
+	result
 }
 
 /// Checks if a string contains only whitespace characters.
@@ -50,57 +104,109 @@ pub(super) fn is_string_effectively_empty(s: &str) -> bool {
 	s.trim().is_empty()
 }
 
-/// Checks if a `<meta>` tag element should be kept based on its `property` attribute.
-pub(super) fn should_keep_meta(element: ElementRef) -> bool {
+/// Checks if a `<meta>` tag element should be kept based on its `property` attribute,
+/// or its `name` attribute when `name` is one of the `SlimOptions::allowed_meta_attrs`.
+pub(super) fn should_keep_meta(element: ElementRef, options: &SlimOptions) -> bool {
 	// Check if the element is actually a <meta> tag
 	if element.value().name() != "meta" {
 		return false;
 	}
 
-	if let Some(prop_value) = element.value().attr("property") {
-		let value_lower = prop_value.to_lowercase();
-		// Check if the property value contains any of the relevant keywords
-		META_PROPERTY_KEYWORDS
-			.iter()
-			.any(|&keyword| value_lower.contains(keyword))
-	} else {
-		// No 'property' attribute found
-		false
-	}
+	// A `name` keyed meta is only kept when its key is output
+	let name = element
+		.value()
+		.attr("name")
+		.filter(|_| options.allowed_meta_attrs.contains("name"));
+	[element.value().attr("property"), name]
+		.into_iter()
+		.flatten()
+		.any(|key| {
+			let key_lower = key.to_lowercase();
+			// Check if the key contains any of the relevant keywords
+			options
+				.meta_property_keywords
+				.iter()
+				.any(|keyword| key_lower.contains(keyword))
+		})
 }
 
 /// Filters attributes of an element and writes the allowed ones to the output string.
+///
+/// The URL attributes are resolved against the `base_url` when given, then passed to the `SlimOptions::rules`.
+/// Returns the number of attributes written.
 pub(super) fn filter_and_write_attributes(
 	element: ElementRef,
 	is_in_head_context: bool,
+	options: &SlimOptions,
+	base_url: Option<&str>,
 	output: &mut String,
-) -> Result<()> {
+) -> Result<usize> {
 	let tag_name = element.value().name();
 
 	// Determine the correct list of allowed attributes based on context
-	let allowed_attrs: &[&str] = if is_in_head_context {
+	let allowed_attrs: Option<&NameSet> = if is_in_head_context {
 		match tag_name {
-			"meta" => ALLOWED_META_ATTRS,
-			"title" => &[], // No attributes allowed on title
-			_ => &[],       // Default deny for other unexpected tags in head
+			"meta" => Some(&options.allowed_meta_attrs),
+			"title" => None, // No attributes allowed on title
+			_ => None,       // Default deny for other unexpected tags in head
 		}
 	} else {
 		// Outside head context
-		ALLOWED_BODY_ATTRS
+		Some(&options.allowed_body_attrs)
+	};
+	let Some(allowed_attrs) = allowed_attrs else {
+		return Ok(0);
 	};
 
-	// Iterate over attributes and append allowed ones
-	for (name, value) in element.value().attrs() {
-		// Check against the determined allowlist
-		if allowed_attrs.contains(&name) {
-			output.push(' ');
-			output.push_str(name);
-			output.push_str("=\"");
-			// Encode attribute value correctly
-			output.push_str(&encode_double_quoted_attribute(value));
-			output.push('"');
-		}
+	// Collect the allowed attributes, sorted by name so the output does not depend on the source order
+	let mut attrs: Vec<(&str, &str)> = element
+		.value()
+		.attrs()
+		.filter(|(name, _)| allowed_attrs.contains(name))
+		.collect();
+	attrs.sort_by(|a, b| a.0.cmp(b.0));
+
+	let mut count = 0;
+	for (name, value) in attrs {
+		let resolved = base_url.and_then(|base_url| resolve_url_attr(name, value, base_url));
+		let value = resolved.as_deref().unwrap_or(value);
+		let action = options
+			.rules
+			.action(|rule| match rule.on_attribute(SlimElement::new(element), name, value) {
+				SlimAction::Unwrap => SlimAction::Keep,
+				action => action,
+			});
+		let (name, value) = match &action {
+			SlimAction::Drop => continue,
+			SlimAction::Rename(new_name) => (new_name.as_str(), value),
+			SlimAction::Rewrite(new_value) => (name, new_value.as_str()),
+			SlimAction::Keep | SlimAction::Unwrap => (name, value),
+		};
+		count += 1;
+		output.push(' ');
+		output.push_str(name);
+		output.push_str("=\"");
+		// Encode attribute value correctly
+		output.push_str(&encode_double_quoted_attribute(value));
+		output.push('"');
 	}
 
-	Ok(())
+	Ok(count)
+}
+
+/// Truncates the text (trimmed) to `max_chars`, at a word boundary when not too far, with a `…` suffix.
+///
+/// The surrounding whitespace is kept. Returns `None` when the text is not longer than `max_chars`.
+pub(super) fn truncate_text(text: &str, max_chars: usize) -> Option<String> {
+	let trimmed = text.trim();
+	let end = trimmed.char_indices().nth(max_chars).map(|(idx, _)| idx)?;
+	let cut = &trimmed[..end];
+	let cut = match cut.rfind(char::is_whitespace) {
+		Some(idx) if idx >= cut.len() / 2 => &cut[..idx],
+		_ => cut,
+	};
+
+	let leading = &text[..text.len() - text.trim_start().len()];
+	let trailing = &text[text.trim_end().len()..];
+	Some(format!("{leading}{}…{trailing}", cut.trim_end()))
 }
@@ -0,0 +1,10 @@
+// region:    --- Modules
+
+mod source_map;
+mod source_span;
+
+pub use source_span::*;
+
+pub(crate) use source_map::SourceMap;
+
+// endregion: --- Modules
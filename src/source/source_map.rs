@@ -0,0 +1,366 @@
+use super::SourceSpan;
+use ego_tree::NodeId;
+use scraper::{Html, node::Node};
+use std::collections::HashMap;
+
+/// Elements whose content is raw text (no tags inside).
+const RAW_TEXT_TAGS: &[&str] = &[
+	"script", "style", "textarea", "title", "xmp", "iframe", "noembed", "noframes", "noscript",
+];
+
+/// Elements the parser creates when they are omitted in the source (never searched ahead).
+const IMPLIED_TAGS: &[&str] = &["html", "head", "body", "tbody", "colgroup"];
+
+/// How many source start tags can be skipped to align a parsed element (tags dropped by the parser).
+const ALIGN_LOOKAHEAD: usize = 8;
+
+/// The `SourceSpan` of the parsed elements, keyed by node id.
+///
+/// The parser does not keep the source positions, so the source is scanned for its tags, and the start tags
+/// are aligned, in order, with the parsed elements (document order). This is best effort:
+/// elements created by the parser (e.g., an implied `<tbody>`) have no span.
+#[derive(Debug, Clone)]
+pub(crate) struct SourceMap {
+	spans: HashMap<NodeId, SourceSpan>,
+}
+
+// region:    --- Constructors
+
+impl SourceMap {
+	pub(crate) fn new(source: &str, html: &Html) -> Self {
+		let tags = scan_tags(source);
+
+		// -- Align the parsed elements (document order) with the source start tags
+		let start_tag_idxs: Vec<usize> = (0..tags.len()).filter(|i| tags[*i].kind == TagKind::Start).collect();
+		let mut cursor = 0;
+		// (node id, tag index) of the elements, in document order
+		let mut elements: Vec<(NodeId, Option<usize>)> = Vec::new();
+		let mut is_aligned = vec![false; tags.len()];
+		let mut positions: Vec<(usize, usize)> = vec![(0, 0); tags.len()];
+		let mut locator = LineLocator::new(source);
+		for node in html.tree.root().descendants() {
+			let Node::Element(el) = node.value() else {
+				continue;
+			};
+			let name = el.name();
+			let lookahead = if IMPLIED_TAGS.contains(&name) {
+				1
+			} else {
+				ALIGN_LOOKAHEAD
+			};
+			let found = start_tag_idxs[cursor.min(start_tag_idxs.len())..]
+				.iter()
+				.take(lookahead)
+				.position(|idx| tags[*idx].name.eq_ignore_ascii_case(name));
+			let tag_idx = found.map(|offset| {
+				cursor += offset + 1;
+				start_tag_idxs[cursor - 1]
+			});
+			if let Some(tag_idx) = tag_idx {
+				is_aligned[tag_idx] = true;
+				positions[tag_idx] = locator.locate(tags[tag_idx].start);
+			}
+			elements.push((node.id(), tag_idx));
+		}
+
+		// -- Find the end of each element, children first (reverse document order)
+		let mut is_used = vec![false; tags.len()];
+		let mut last_tag_idxs: HashMap<NodeId, usize> = HashMap::new();
+		let mut spans = HashMap::new();
+		for (id, tag_idx) in elements.into_iter().rev() {
+			let Some(node) = html.tree.get(id) else {
+				continue;
+			};
+			let children_last = node
+				.children()
+				.filter_map(|child| last_tag_idxs.get(&child.id()).copied())
+				.max();
+			let last = tag_idx.max(children_last);
+
+			if let Some(tag_idx) = tag_idx {
+				let name = &tags[tag_idx].name;
+				let ancestor_names: Vec<&str> = node
+					.ancestors()
+					.filter_map(|n| n.value().as_element().map(|el| el.name()))
+					.collect();
+				let search_from = last.unwrap_or(tag_idx) + 1;
+
+				let mut end = None;
+				let mut end_tag_idx = None;
+				for (idx, tag) in tags.iter().enumerate().skip(search_from) {
+					match tag.kind {
+						TagKind::Start if is_aligned[idx] => {
+							end = Some(trim_end_offset(source, tags[tag_idx].end, tag.start));
+							break;
+						}
+						TagKind::End if !is_used[idx] && &tag.name == name => {
+							is_used[idx] = true;
+							end_tag_idx = Some(idx);
+							end = Some(tag.end);
+							break;
+						}
+						TagKind::End
+							if !is_used[idx] && ancestor_names.iter().any(|n| n.eq_ignore_ascii_case(&tag.name)) =>
+						{
+							end = Some(trim_end_offset(source, tags[tag_idx].end, tag.start));
+							break;
+						}
+						_ => (),
+					}
+				}
+				let end = end.unwrap_or_else(|| trim_end_offset(source, tags[tag_idx].end, source.len()));
+
+				let start = tags[tag_idx].start;
+				let (line, column) = positions[tag_idx];
+				spans.insert(
+					id,
+					SourceSpan {
+						start,
+						end: end.max(tags[tag_idx].end),
+						line,
+						column,
+					},
+				);
+
+				if let Some(last) = end_tag_idx.max(last) {
+					last_tag_idxs.insert(id, last);
+				}
+			} else if let Some(last) = last {
+				last_tag_idxs.insert(id, last);
+			}
+		}
+
+		Self { spans }
+	}
+}
+
+// endregion: --- Constructors
+
+// region:    --- Accessors
+
+impl SourceMap {
+	pub(crate) fn get(&self, id: NodeId) -> Option<SourceSpan> {
+		self.spans.get(&id).copied()
+	}
+}
+
+// endregion: --- Accessors
+
+// region:    --- Scanner
+
+#[derive(Debug, PartialEq, Eq)]
+enum TagKind {
+	Start,
+	End,
+}
+
+/// A tag of the HTML source.
+#[derive(Debug)]
+struct Tag {
+	kind: TagKind,
+	/// Lowercase tag name.
+	name: String,
+	/// Byte offset of the `<`.
+	start: usize,
+	/// Byte offset after the `>`.
+	end: usize,
+}
+
+/// Scans the start and end tags of the HTML source, skipping comments, doctype, and raw text content.
+fn scan_tags(source: &str) -> Vec<Tag> {
+	let bytes = source.as_bytes();
+	let len = bytes.len();
+	let mut tags = Vec::new();
+	let mut i = 0;
+
+	while let Some(offset) = bytes[i..].iter().position(|b| *b == b'<') {
+		let start = i + offset;
+		let rest = &bytes[start..];
+
+		if rest.starts_with(b"<!--") {
+			i = find(bytes, start + 4, b"-->").map(|p| p + 3).unwrap_or(len);
+			continue;
+		}
+		if matches!(rest.get(1), Some(b'!' | b'?')) {
+			i = find(bytes, start + 2, b">").map(|p| p + 1).unwrap_or(len);
+			continue;
+		}
+
+		let (kind, name_start) = if rest.get(1) == Some(&b'/') {
+			(TagKind::End, start + 2)
+		} else {
+			(TagKind::Start, start + 1)
+		};
+		if !bytes.get(name_start).is_some_and(u8::is_ascii_alphabetic) {
+			i = start + 1;
+			continue;
+		}
+
+		let name_end = bytes[name_start..]
+			.iter()
+			.position(|b| b.is_ascii_whitespace() || *b == b'/' || *b == b'>')
+			.map(|p| name_start + p)
+			.unwrap_or(len);
+		let name = source[name_start..name_end].to_ascii_lowercase();
+		let end = tag_end(bytes, name_end);
+
+		i = end;
+		if kind == TagKind::Start {
+			if name == "plaintext" {
+				i = len;
+			} else if RAW_TEXT_TAGS.contains(&name.as_str()) {
+				i = find_end_tag(bytes, end, &name).unwrap_or(len);
+			}
+		}
+
+		tags.push(Tag { kind, name, start, end });
+	}
+
+	tags
+}
+
+/// Returns the offset after the `>` closing the tag, skipping the quoted attribute values.
+fn tag_end(bytes: &[u8], from: usize) -> usize {
+	let len = bytes.len();
+	let mut j = from;
+	while j < len {
+		match bytes[j] {
+			b'>' => return j + 1,
+			b'=' => {
+				j += 1;
+				while j < len && bytes[j].is_ascii_whitespace() {
+					j += 1;
+				}
+				if j < len && matches!(bytes[j], b'"' | b'\'') {
+					let quote = [bytes[j]];
+					j = find(bytes, j + 1, &quote).map(|p| p + 1).unwrap_or(len);
+				}
+			}
+			_ => j += 1,
+		}
+	}
+	len
+}
+
+/// Returns the offset of the `</name` end tag (case-insensitive).
+fn find_end_tag(bytes: &[u8], from: usize, name: &str) -> Option<usize> {
+	let name = name.as_bytes();
+	let mut j = from;
+	while let Some(p) = find(bytes, j, b"</") {
+		let name_start = p + 2;
+		if bytes
+			.get(name_start..name_start + name.len())
+			.is_some_and(|n| n.eq_ignore_ascii_case(name))
+		{
+			return Some(p);
+		}
+		j = p + 2;
+	}
+	None
+}
+
+fn find(bytes: &[u8], from: usize, needle: &[u8]) -> Option<usize> {
+	if from >= bytes.len() {
+		return None;
+	}
+	bytes[from..]
+		.windows(needle.len())
+		.position(|w| w == needle)
+		.map(|p| from + p)
+}
+
+/// Moves `end` back over the trailing whitespace, not before `min`.
+fn trim_end_offset(source: &str, min: usize, end: usize) -> usize {
+	let trimmed = source[..end].trim_end_matches(|c: char| c.is_ascii_whitespace()).len();
+	trimmed.max(min)
+}
+
+/// Computes the 1-based line and column (in characters) of increasing byte offsets, in a single pass.
+struct LineLocator<'a> {
+	source: &'a str,
+	offset: usize,
+	line: usize,
+	column: usize,
+}
+
+impl<'a> LineLocator<'a> {
+	fn new(source: &'a str) -> Self {
+		Self {
+			source,
+			offset: 0,
+			line: 1,
+			column: 1,
+		}
+	}
+
+	/// The `offset` must be greater or equal to the previous one.
+	fn locate(&mut self, offset: usize) -> (usize, usize) {
+		for c in self.source[self.offset..offset].chars() {
+			if c == '\n' {
+				self.line += 1;
+				self.column = 1;
+			} else {
+				self.column += 1;
+			}
+		}
+		self.offset = offset;
+		(self.line, self.column)
+	}
+}
+
+// endregion: --- Scanner
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	type TestResult<T> = core::result::Result<T, Box<dyn std::error::Error>>;
+
+	use super::*;
+	use scraper::Selector;
+
+	fn span_of(source: &str, selector: &str) -> TestResult<Option<SourceSpan>> {
+		let html = Html::parse_document(source);
+		let map = SourceMap::new(source, &html);
+		let selector = Selector::parse(selector).map_err(|err| err.to_string())?;
+		let el = html.select(&selector).next().ok_or("should match")?;
+		Ok(map.get(el.id()))
+	}
+
+	#[test]
+	fn test_source_map_spans() -> TestResult<()> {
+		// -- Setup & Fixtures
+		let fx_html = "<div>\n  <p class=\"a>b\">Hi <b>you</b></p>\n  <script>let s = '<p>';</script>\n  <ul><li>A<li>B</ul>\n</div>";
+
+		// -- Exec
+		let p = span_of(fx_html, "div > p")?.ok_or("p should have a span")?;
+		let li = span_of(fx_html, "li")?.ok_or("li should have a span")?;
+		let ul = span_of(fx_html, "ul")?.ok_or("ul should have a span")?;
+
+		// -- Check
+		assert_eq!(&fx_html[p.start..p.end], "<p class=\"a>b\">Hi <b>you</b></p>");
+		assert_eq!((p.line, p.column), (2, 3));
+		assert_eq!(&fx_html[li.start..li.end], "<li>A");
+		assert_eq!(&fx_html[ul.start..ul.end], "<ul><li>A<li>B</ul>");
+		assert_eq!(ul.line, 4);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_source_map_implied_elements() -> TestResult<()> {
+		// -- Setup & Fixtures
+		let fx_html = "<table><tr><td>1</td></tr></table>";
+
+		// -- Exec & Check
+		assert_eq!(span_of(fx_html, "tbody")?, None);
+		let td = span_of(fx_html, "td")?.ok_or("td should have a span")?;
+		assert_eq!(&fx_html[td.start..td.end], "<td>1</td>");
+		let table = span_of(fx_html, "table")?.ok_or("table should have a span")?;
+		assert_eq!((table.start, table.end), (0, fx_html.len()));
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+
+/// The location of an element in the HTML source.
+///
+/// `start..end` is the byte range of the element, from its start tag to its end tag
+/// (or to the next tag when the end tag is omitted, e.g., `<li>A<li>B`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SourceSpan {
+	/// Byte offset of the start tag `<`.
+	pub start: usize,
+	/// Byte offset after the element (exclusive).
+	pub end: usize,
+	/// Line of `start` (1-based).
+	pub line: usize,
+	/// Column of `start`, in characters (1-based).
+	pub column: usize,
+}
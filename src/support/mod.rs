@@ -0,0 +1,9 @@
+// region:    --- Modules
+
+mod tags;
+mod text;
+
+pub(crate) use tags::*;
+pub(crate) use text::*;
+
+// endregion: --- Modules
@@ -0,0 +1,34 @@
+// region:    --- Constants
+
+/// Tags considered block-level for layout purposes (indentation in `slim`, line breaks in `to_text`).
+#[rustfmt::skip]
+pub(crate) const BLOCK_LEVEL_TAGS: &[&str] = &[
+	"html", "head", "body", "a", "div", "p", "section", "article", "header", "footer", "nav", "aside",
+	"ul", "ol", "li", "table", "tr", "td", "th", "h1", "h2", "h3", "h4", "h5", "h6",
+	"pre", "blockquote", "main", "form", "fieldset", "details", "summary", "figure", "figcaption",
+	"dl", "dt", "dd", "br", "hr", "img", "input", "meta", "link", "script", "style",
+];
+
+/// Tags of `BLOCK_LEVEL_TAGS` that are only block-level for `slim` formatting, but flow inline in rendered text.
+pub(crate) const INLINE_FLOW_TAGS: &[&str] = &["a", "img", "input", "br"];
+
+/// HTML void elements (should not have closing tags).
+#[rustfmt::skip]
+pub(crate) const VOID_ELEMENTS: &[&str] = &[
+	"area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta",
+	"param", "source", "track", "wbr",
+];
+
+/// Tags never rendered as content (with all their content), for text and markdown rendering.
+#[rustfmt::skip]
+pub(crate) const NON_CONTENT_TAGS: &[&str] = &[
+	"head", "title", "meta", "link", "base", "script", "style", "noscript", "template",
+	"svg", "canvas", "iframe", "object", "embed",
+];
+
+// endregion: --- Constants
+
+/// Returns true if the tag starts a new line when rendered as text.
+pub(crate) fn is_text_block_tag(tag: &str) -> bool {
+	BLOCK_LEVEL_TAGS.contains(&tag) && !INLINE_FLOW_TAGS.contains(&tag)
+}
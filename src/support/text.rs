@@ -0,0 +1,17 @@
+/// Collapses the HTML whitespace runs into a single space.
+pub(crate) fn collapse_whitespace(text: &str) -> String {
+	let mut out = String::with_capacity(text.len());
+	let mut in_space = false;
+	for c in text.chars() {
+		if c.is_whitespace() {
+			if !in_space {
+				out.push(' ');
+			}
+			in_space = true;
+		} else {
+			out.push(c);
+			in_space = false;
+		}
+	}
+	out
+}
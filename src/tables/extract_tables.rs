@@ -0,0 +1,284 @@
+use super::Table;
+use crate::support::collapse_whitespace;
+use crate::{Document, Result, TextOptions, build_selector, text_from_node};
+use scraper::ElementRef;
+
+// region:    --- Constants
+
+/// The maximum `colspan` (as browsers).
+const MAX_COLSPAN: usize = 1000;
+
+// endregion: --- Constants
+
+/// Extracts the `<table>` elements matching the CSS selectors (e.g., `["table.data"]`) as `Table` grids,
+/// in document order. The matched elements that are not tables are skipped.
+///
+/// - `rowspan`/`colspan` cells are repeated in each slot they span (a `rowspan` stops at the end of its row group,
+///   and `rowspan="0"` spans to it).
+/// - The header rows are the `<thead>` rows, or, without `<thead>`, the leading rows made of `<th>` cells only.
+/// - The cell texts are rendered as `to_text` does, with the whitespace collapsed.
+/// - The rows of nested tables are not part of the outer table (only their text, in the outer cell).
+pub fn extract_tables<S>(html_content: &str, selectors: S) -> Result<Vec<Table>>
+where
+	S: IntoIterator,
+	S::Item: AsRef<str>,
+{
+	Document::parse(html_content).tables(selectors)
+}
+
+/// Extracts the tables of an already parsed document (see `Document::tables`).
+pub(crate) fn tables_from_document<S>(doc: &Document, selectors: S) -> Result<Vec<Table>>
+where
+	S: IntoIterator,
+	S::Item: AsRef<str>,
+{
+	let Some(selector) = build_selector(selectors)? else {
+		return Ok(Vec::new());
+	};
+	let tables = doc
+		.html()
+		.select(&selector)
+		.filter(|el| el.value().name() == "table")
+		.map(table_from_element)
+		.collect();
+	Ok(tables)
+}
+
+// region:    --- Grid
+
+/// A row of the table, with the index of its row group (`<thead>`, `<tbody>`, `<tfoot>`).
+struct RowRef<'a> {
+	row: ElementRef<'a>,
+	group: usize,
+}
+
+fn table_from_element(table: ElementRef) -> Table {
+	let mut caption = None;
+	let (mut head, mut body, mut foot): (Vec<RowRef>, Vec<RowRef>, Vec<RowRef>) = (Vec::new(), Vec::new(), Vec::new());
+
+	for (group, child) in table.children().filter_map(ElementRef::wrap).enumerate() {
+		match child.value().name() {
+			"caption" if caption.is_none() => caption = Some(cell_text(child)).filter(|text| !text.is_empty()),
+			"thead" => head.extend(section_rows(child, group)),
+			"tbody" => body.extend(section_rows(child, group)),
+			"tfoot" => foot.extend(section_rows(child, group)),
+			// Rows directly in the table (not produced by the parser, which adds a `<tbody>`, but safe)
+			"tr" => body.push(RowRef { row: child, group }),
+			_ => (),
+		}
+	}
+
+	let header_count = if head.is_empty() {
+		body.iter().take_while(|row_ref| is_header_row(row_ref.row)).count()
+	} else {
+		head.len()
+	};
+	let rows: Vec<RowRef> = head.into_iter().chain(body).chain(foot).collect();
+	let mut grid = build_grid(&rows);
+
+	let rows = grid.split_off(header_count);
+	let headers = merge_header_rows(&grid);
+
+	Table { caption, headers, rows }
+}
+
+/// The `<tr>` children of a row group.
+fn section_rows(section: ElementRef, group: usize) -> impl Iterator<Item = RowRef> {
+	section
+		.children()
+		.filter_map(ElementRef::wrap)
+		.filter(|row| row.value().name() == "tr")
+		.map(move |row| RowRef { row, group })
+}
+
+/// Places the cells in the grid, expanding the spans, and pads the rows to the table width.
+fn build_grid(rows: &[RowRef]) -> Vec<Vec<String>> {
+	let mut slots: Vec<Vec<Option<String>>> = vec![Vec::new(); rows.len()];
+
+	// The end (exclusive) of the row group of each row, as the rowspans stop there
+	let mut group_ends = vec![rows.len(); rows.len()];
+	for row_idx in (0..rows.len().saturating_sub(1)).rev() {
+		if rows[row_idx + 1].group == rows[row_idx].group {
+			group_ends[row_idx] = group_ends[row_idx + 1];
+		} else {
+			group_ends[row_idx] = row_idx + 1;
+		}
+	}
+
+	for (row_idx, row_ref) in rows.iter().enumerate() {
+		let group_end = group_ends[row_idx];
+		let mut col = 0;
+
+		for cell in row_ref.row.children().filter_map(ElementRef::wrap) {
+			if !matches!(cell.value().name(), "td" | "th") {
+				continue;
+			}
+			// Skip the slots taken by the rowspans of the previous rows
+			while slots[row_idx].get(col).is_some_and(Option::is_some) {
+				col += 1;
+			}
+
+			let colspan = span_attr(cell, "colspan").unwrap_or(1).clamp(1, MAX_COLSPAN);
+			let rowspan = match span_attr(cell, "rowspan") {
+				Some(0) => group_end - row_idx,
+				Some(rowspan) => rowspan.min(group_end - row_idx),
+				None => 1,
+			};
+			let text = cell_text(cell);
+
+			for row_slots in &mut slots[row_idx..row_idx + rowspan] {
+				if row_slots.len() < col + colspan {
+					row_slots.resize(col + colspan, None);
+				}
+				for slot in &mut row_slots[col..col + colspan] {
+					if slot.is_none() {
+						*slot = Some(text.clone());
+					}
+				}
+			}
+			col += colspan;
+		}
+	}
+
+	let width = slots.iter().map(Vec::len).max().unwrap_or(0);
+	slots
+		.into_iter()
+		.map(|row_slots| {
+			let mut row: Vec<String> = row_slots.into_iter().map(Option::unwrap_or_default).collect();
+			row.resize(width, String::new());
+			row
+		})
+		.collect()
+}
+
+/// One name per column: the distinct non-empty names of the header rows, joined with ` / `.
+fn merge_header_rows(header_rows: &[Vec<String>]) -> Vec<String> {
+	let width = header_rows.first().map_or(0, Vec::len);
+	(0..width)
+		.map(|col| {
+			let mut names: Vec<&str> = Vec::new();
+			for name in header_rows.iter().map(|row| row[col].as_str()) {
+				if !name.is_empty() && names.last() != Some(&name) {
+					names.push(name);
+				}
+			}
+			names.join(" / ")
+		})
+		.collect()
+}
+
+// endregion: --- Grid
+
+// region:    --- Support
+
+/// Whether the row has cells, all `<th>`.
+fn is_header_row(row: ElementRef) -> bool {
+	let mut cells = row
+		.children()
+		.filter_map(ElementRef::wrap)
+		.filter(|cell| matches!(cell.value().name(), "td" | "th"))
+		.peekable();
+	cells.peek().is_some() && cells.all(|cell| cell.value().name() == "th")
+}
+
+/// The leading digits of the span attribute (e.g., `2`, ` 3 `, `2px`), as browsers read them.
+fn span_attr(cell: ElementRef, name: &str) -> Option<usize> {
+	let value = cell.value().attr(name)?.trim();
+	let digits_len = value.find(|c: char| !c.is_ascii_digit()).unwrap_or(value.len());
+	value[..digits_len].parse().ok()
+}
+
+fn cell_text(cell: ElementRef) -> String {
+	collapse_whitespace(&text_from_node(*cell, &TextOptions::default()))
+		.trim()
+		.to_string()
+}
+
+// endregion: --- Support
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	type TestResult<T> = core::result::Result<T, Box<dyn std::error::Error>>;
+
+	use super::*;
+	use serde_json::json;
+
+	#[test]
+	fn test_tables_extract_tables_spans_and_headers() -> TestResult<()> {
+		// -- Setup & Fixtures
+		let fx_html = r#"
+		<table class="sales">
+			<caption>Sales  2024</caption>
+			<thead>
+				<tr><th rowspan="2">Region</th><th colspan="2">Sales</th></tr>
+				<tr><th>Q1</th><th>Q2</th></tr>
+			</thead>
+			<tbody>
+				<tr><td rowspan="2">North</td><td>10</td><td>12</td></tr>
+				<tr><td colspan="2">n/a, "closed"</td></tr>
+				<tr><td>South</td><td>7<br>(est.)</td></tr>
+			</tbody>
+		</table>
+		<table><tr><td>Other</td></tr></table>
+		"#;
+
+		// -- Exec
+		let tables = extract_tables(fx_html, ["table.sales"])?;
+
+		// -- Check
+		assert_eq!(tables.len(), 1);
+		let table = &tables[0];
+		assert_eq!(table.caption.as_deref(), Some("Sales 2024"));
+		assert_eq!(table.headers, vec!["Region", "Sales / Q1", "Sales / Q2"]);
+		assert_eq!(
+			table.rows,
+			vec![
+				vec!["North", "10", "12"],
+				vec!["North", r#"n/a, "closed""#, r#"n/a, "closed""#],
+				vec!["South", "7 (est.)", ""],
+			]
+		);
+		assert_eq!(
+			table.to_csv(),
+			"Region,Sales / Q1,Sales / Q2\nNorth,10,12\nNorth,\"n/a, \"\"closed\"\"\",\"n/a, \"\"closed\"\"\"\nSouth,7 (est.),\n"
+		);
+		assert_eq!(
+			table.records()[0],
+			json!({"Region": "North", "Sales / Q1": "10", "Sales / Q2": "12"})
+		);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_tables_extract_tables_th_header_and_no_header() -> TestResult<()> {
+		// -- Setup & Fixtures
+		let fx_html = r#"
+		<table id="a">
+			<tr><th>Name</th><th>Name</th><th></th></tr>
+			<tr><th>Ann</th><td>1</td><td rowspan="0">x</td></tr>
+			<tr><th>Bob</th><td>2</td></tr>
+		</table>
+		<table id="b"><tr><td>1</td><td>2<table><tr><td>nested</td></tr></table></td></tr></table>
+		"#;
+
+		// -- Exec
+		let tables = extract_tables(fx_html, ["#a", "#b"])?;
+
+		// -- Check
+		let (a, b) = (&tables[0], &tables[1]);
+		assert_eq!(a.headers, vec!["Name", "Name", ""]);
+		assert_eq!(a.rows, vec![vec!["Ann", "1", "x"], vec!["Bob", "2", "x"]]);
+		assert_eq!(a.records()[1], json!({"Name": "Bob", "Name_2": "2", "col_3": "x"}));
+		assert!(b.headers.is_empty());
+		assert_eq!(b.rows, vec![vec!["1", "2 nested"]]);
+		assert_eq!(b.grid(), b.rows);
+		assert_eq!(b.records(), json!([{"col_1": "1", "col_2": "2 nested"}]));
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
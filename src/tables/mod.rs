@@ -0,0 +1,9 @@
+// region:    --- Modules
+
+mod extract_tables;
+mod table;
+
+pub use extract_tables::*;
+pub use table::*;
+
+// endregion: --- Modules
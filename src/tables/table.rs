@@ -0,0 +1,107 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashSet;
+
+// region:    --- Types
+
+/// A `<table>` as a grid of cell texts, with the `rowspan`/`colspan` cells repeated in each slot they span
+/// (see `extract_tables`).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Table {
+	/// The `<caption>` text.
+	pub caption: Option<String>,
+	/// The column names, from the header rows (empty when the table has no header row).
+	///
+	/// With several header rows, the distinct names of a column are joined with ` / ` (e.g., `Sales / Q1`).
+	pub headers: Vec<String>,
+	/// The body rows (including `<tfoot>`), each as wide as the table.
+	pub rows: Vec<Vec<String>>,
+}
+
+// endregion: --- Types
+
+// region:    --- Outputs
+
+impl Table {
+	/// The number of columns.
+	pub fn width(&self) -> usize {
+		self.rows.first().map_or(self.headers.len(), Vec::len)
+	}
+
+	/// The headers (when any) followed by the rows.
+	pub fn grid(&self) -> Vec<Vec<String>> {
+		let header = (!self.headers.is_empty()).then(|| self.headers.clone());
+		header.into_iter().chain(self.rows.iter().cloned()).collect()
+	}
+
+	/// The rows as a JSON array of objects keyed by header.
+	///
+	/// Empty headers (or all of them without header row) are named `col_<n>` (1-based), and duplicate ones get
+	/// a `_<n>` suffix (e.g., `Name`, `Name_2`).
+	pub fn records(&self) -> Value {
+		let keys = self.record_keys();
+		let records = self
+			.rows
+			.iter()
+			.map(|row| {
+				let record: Map<String, Value> = keys
+					.iter()
+					.zip(row.iter())
+					.map(|(key, cell)| (key.clone(), Value::String(cell.clone())))
+					.collect();
+				Value::Object(record)
+			})
+			.collect();
+		Value::Array(records)
+	}
+
+	/// The grid as CSV text (RFC 4180 quoting, one `\n` terminated line per row).
+	pub fn to_csv(&self) -> String {
+		let mut csv = String::new();
+		for row in self.grid() {
+			let line: Vec<String> = row.iter().map(|cell| csv_field(cell)).collect();
+			csv.push_str(&line.join(","));
+			csv.push('\n');
+		}
+		csv
+	}
+}
+
+// endregion: --- Outputs
+
+// region:    --- Support
+
+impl Table {
+	/// The unique record keys, one per column.
+	fn record_keys(&self) -> Vec<String> {
+		let mut seen: HashSet<String> = HashSet::new();
+		(0..self.width())
+			.map(|idx| {
+				let name = self.headers.get(idx).map(|h| h.trim()).unwrap_or_default();
+				let name = if name.is_empty() {
+					format!("col_{}", idx + 1)
+				} else {
+					name.to_string()
+				};
+				let mut key = name.clone();
+				let mut n = 2;
+				while !seen.insert(key.clone()) {
+					key = format!("{name}_{n}");
+					n += 1;
+				}
+				key
+			})
+			.collect()
+	}
+}
+
+/// The cell quoted when it contains a comma, a double quote, or a line break.
+fn csv_field(cell: &str) -> String {
+	if cell.contains([',', '"', '\n', '\r']) {
+		format!("\"{}\"", cell.replace('"', "\"\""))
+	} else {
+		cell.to_string()
+	}
+}
+
+// endregion: --- Support
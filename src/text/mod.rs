@@ -0,0 +1,9 @@
+// region:    --- Modules
+
+mod text_options;
+mod to_text;
+
+pub use text_options::*;
+pub use to_text::*;
+
+// endregion: --- Modules
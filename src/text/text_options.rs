@@ -0,0 +1,30 @@
+// region:    --- Types
+
+/// Options for the `to_text` function.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TextOptions {
+	/// Prefix list items with `- ` (or `1. ` for ordered lists), indented by nesting level.
+	pub list_bullets: bool,
+	/// Render table rows on one line, with the cells separated by ` | `.
+	pub table_cells: bool,
+}
+
+// endregion: --- Types
+
+// region:    --- Constructors & Fluid API
+
+impl TextOptions {
+	/// Render list bullets.
+	pub fn with_list_bullets(mut self, list_bullets: bool) -> Self {
+		self.list_bullets = list_bullets;
+		self
+	}
+
+	/// Render table rows on one line, cells separated by ` | `.
+	pub fn with_table_cells(mut self, table_cells: bool) -> Self {
+		self.table_cells = table_cells;
+		self
+	}
+}
+
+// endregion: --- Constructors & Fluid API
@@ -0,0 +1,295 @@
+use super::TextOptions;
+use crate::{Document, Result};
+use crate::support::{NON_CONTENT_TAGS, is_text_block_tag};
+use ego_tree::NodeRef;
+use scraper::{ElementRef, node::Node};
+
+/// Tags separated from their siblings by a blank line (other block-level tags get a single line break).
+const PARAGRAPH_TAGS: &[&str] = &["p", "h1", "h2", "h3", "h4", "h5", "h6", "pre", "blockquote"];
+
+/// Renders HTML content as plain text, following the HTML layout.
+///
+/// - Block-level elements start on a new line (blank line for paragraphs, headings, `<pre>`, and `<blockquote>`).
+/// - Inline whitespace is collapsed as a browser would, and `<br>` breaks the line.
+/// - `<pre>` content is preserved as is.
+/// - Non-content elements (`<head>`, `<script>`, `<style>`, etc.) are skipped.
+///
+/// # Arguments
+///
+/// * `html_content` - A string slice containing the HTML content to render.
+/// * `options` - The `TextOptions` (list bullets, table cells).
+///
+/// # Returns
+///
+/// A `Result<String>` containing the rendered text (trimmed).
+pub fn to_text(html_content: &str, options: impl Into<TextOptions>) -> Result<String> {
+	Ok(Document::parse(html_content).text(options))
+}
+
+/// Renders the text of a parsed node (document, fragment, or element).
+pub(crate) fn text_from_node(node: NodeRef<Node>, options: &TextOptions) -> String {
+	text_from_nodes([node], options)
+}
+
+/// Renders the text of sibling nodes, as a single flow (e.g., the inline content of a block).
+pub(crate) fn text_from_nodes<'a>(nodes: impl IntoIterator<Item = NodeRef<'a, Node>>, options: &TextOptions) -> String {
+	let mut writer = TextWriter::default();
+	let mut stack: Vec<Step> = nodes.into_iter().map(Step::Node).collect();
+	stack.reverse();
+	// Walked with a stack (no recursion), so deep documents are safe
+	while let Some(step) = stack.pop() {
+		match step {
+			Step::Node(node) => write_node(node, options, &mut writer, &mut stack),
+			Step::Breaks(count) => writer.request_breaks(count),
+		}
+	}
+	writer.finish()
+}
+
+/// The work of the text walk: a node to write, or the line breaks required after a block.
+enum Step<'a> {
+	Node(NodeRef<'a, Node>),
+	Breaks(usize),
+}
+
+/// Writes the node start, and pushes its children (and its end) on the stack.
+fn write_node<'a>(node: NodeRef<'a, Node>, options: &TextOptions, writer: &mut TextWriter, stack: &mut Vec<Step<'a>>) {
+	match node.value() {
+		Node::Document | Node::Fragment => push_children(node, stack),
+		Node::Text(text) => writer.push_text(text),
+		Node::Element(el) => {
+			let tag = el.name();
+			if NON_CONTENT_TAGS.contains(&tag) {
+				return;
+			}
+			let Some(el_ref) = ElementRef::wrap(node) else {
+				return;
+			};
+
+			match tag {
+				"br" => writer.push_line_break(),
+				"pre" => {
+					writer.request_breaks(2);
+					writer.push_raw(&el_ref.text().collect::<String>());
+					writer.request_breaks(2);
+				}
+				"li" if options.list_bullets => {
+					writer.request_breaks(1);
+					writer.push_prefix(&list_bullet(el_ref));
+					stack.push(Step::Breaks(1));
+					push_children(node, stack);
+				}
+				"td" | "th" if options.table_cells => {
+					let is_first_cell = el_ref
+						.prev_siblings()
+						.filter_map(ElementRef::wrap)
+						.all(|s| !matches!(s.value().name(), "td" | "th"));
+					if !is_first_cell {
+						writer.push_prefix(" | ");
+					}
+					push_children(node, stack);
+				}
+				_ if is_text_block_tag(tag) => {
+					let breaks = if PARAGRAPH_TAGS.contains(&tag) { 2 } else { 1 };
+					writer.request_breaks(breaks);
+					stack.push(Step::Breaks(breaks));
+					push_children(node, stack);
+				}
+				_ => push_children(node, stack),
+			}
+		}
+		_ => {}
+	}
+}
+
+/// Pushes the children in reverse order, so they are written in order.
+fn push_children<'a>(node: NodeRef<'a, Node>, stack: &mut Vec<Step<'a>>) {
+	stack.extend(node.children().rev().map(Step::Node));
+}
+
+/// Returns the bullet (`- ` or `n. `) of a list item, indented by its list nesting level.
+fn list_bullet(li: ElementRef) -> String {
+	let lists: Vec<ElementRef> = li
+		.ancestors()
+		.filter_map(ElementRef::wrap)
+		.filter(|a| matches!(a.value().name(), "ul" | "ol"))
+		.collect();
+	let indent = "  ".repeat(lists.len().saturating_sub(1));
+
+	match lists.first() {
+		Some(list) if list.value().name() == "ol" => {
+			let start: usize = list.value().attr("start").and_then(|s| s.trim().parse().ok()).unwrap_or(1);
+			let idx = li
+				.prev_siblings()
+				.filter_map(ElementRef::wrap)
+				.filter(|s| s.value().name() == "li")
+				.count();
+			format!("{indent}{}. ", start + idx)
+		}
+		_ => format!("{indent}- "),
+	}
+}
+
+// region:    --- TextWriter
+
+/// Accumulates text with the HTML whitespace rules (collapsing, pending line breaks).
+#[derive(Default)]
+struct TextWriter {
+	out: String,
+	/// Number of trailing newlines required before the next content.
+	pending_breaks: usize,
+	/// A collapsed whitespace is pending before the next content.
+	pending_space: bool,
+}
+
+impl TextWriter {
+	fn push_text(&mut self, text: &str) {
+		for c in text.chars() {
+			if c.is_whitespace() {
+				self.pending_space = true;
+			} else {
+				self.flush_pending();
+				self.out.push(c);
+			}
+		}
+	}
+
+	/// Pushes text as is (e.g., `<pre>` content).
+	fn push_raw(&mut self, text: &str) {
+		if text.is_empty() {
+			return;
+		}
+		self.pending_space = false;
+		self.flush_pending();
+		self.out.push_str(text);
+	}
+
+	/// Pushes a prefix (bullet, cell separator), with no collapsed space after it.
+	fn push_prefix(&mut self, prefix: &str) {
+		self.pending_space = false;
+		self.flush_pending();
+		self.out.push_str(prefix);
+	}
+
+	fn push_line_break(&mut self) {
+		self.flush_breaks();
+		self.trim_trailing_spaces();
+		self.out.push('\n');
+		self.pending_space = false;
+	}
+
+	/// Requires at least `count` newlines before the next content.
+	fn request_breaks(&mut self, count: usize) {
+		self.pending_breaks = self.pending_breaks.max(count);
+		self.pending_space = false;
+	}
+
+	fn flush_pending(&mut self) {
+		self.flush_breaks();
+		if self.pending_space && !self.out.is_empty() && !self.out.ends_with(['\n', ' ']) {
+			self.out.push(' ');
+		}
+		self.pending_space = false;
+	}
+
+	fn flush_breaks(&mut self) {
+		if self.pending_breaks > 0 && !self.out.is_empty() {
+			self.trim_trailing_spaces();
+			let existing = self.out.len() - self.out.trim_end_matches('\n').len();
+			for _ in existing..self.pending_breaks {
+				self.out.push('\n');
+			}
+		}
+		self.pending_breaks = 0;
+	}
+
+	fn trim_trailing_spaces(&mut self) {
+		let len = self.out.trim_end_matches([' ', '\t']).len();
+		self.out.truncate(len);
+	}
+
+	fn finish(self) -> String {
+		self.out.trim().to_string()
+	}
+}
+
+// endregion: --- TextWriter
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	type TestResult<T> = core::result::Result<T, Box<dyn std::error::Error>>;
+
+	use super::*;
+
+	#[test]
+	fn test_text_to_text_blocks_and_whitespace() -> TestResult<()> {
+		// -- Setup & Fixtures
+		let fx_html = r#"
+		<html>
+		<head><title>Ignored</title></head>
+		<body>
+			<h1>  Title  </h1>
+			<p>Some    <b>bold</b>
+			text.<br>Next   line.</p>
+			<ul><li>A</li><li>B</li></ul>
+			<div>Div <span>inline</span></div>
+			<pre>  keep
+    this</pre>
+			<script>ignored()</script>
+		</body>
+		</html>
+		"#;
+
+		// -- Exec
+		let text = to_text(fx_html, TextOptions::default())?;
+
+		// -- Check
+		assert_eq!(text, "Title\n\nSome bold text.\nNext line.\n\nA\nB\nDiv inline\n\n  keep\n    this");
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_text_to_text_list_bullets_and_table_cells() -> TestResult<()> {
+		// -- Setup & Fixtures
+		let fx_html = r#"
+		<ul>
+			<li>One
+				<ol start="3"><li>Three</li><li>Four</li></ol>
+			</li>
+			<li>Two</li>
+		</ul>
+		<table>
+			<tr><th>Name</th><th>Value</th></tr>
+			<tr><td>a</td><td>1</td></tr>
+		</table>
+		"#;
+		let options = TextOptions::default().with_list_bullets(true).with_table_cells(true);
+
+		// -- Exec
+		let text = to_text(fx_html, options)?;
+
+		// -- Check
+		assert_eq!(text, "- One\n  3. Three\n  4. Four\n- Two\nName | Value\na | 1");
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_text_to_text_deep_nesting() -> TestResult<()> {
+		// -- Setup & Fixtures
+		let fx_html = format!("{}<p>Deep</p>{}after", "<div>".repeat(2000), "</div>".repeat(2000));
+
+		// -- Exec
+		let text = to_text(&fx_html, TextOptions::default())?;
+
+		// -- Check
+		assert_eq!(text, "Deep\n\nafter");
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
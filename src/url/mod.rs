@@ -0,0 +1,11 @@
+// region:    --- Modules
+
+mod resolve_url;
+mod url_attrs;
+
+pub use resolve_url::*;
+
+pub(crate) use resolve_url::{url_host, url_scheme};
+pub(crate) use url_attrs::{effective_base_url, resolve_url_attr};
+
+// endregion: --- Modules
@@ -0,0 +1,360 @@
+// region:    --- Types
+
+/// The components of a URL or URL reference (RFC 3986), as slices of the original string.
+struct UrlParts<'a> {
+	scheme: Option<&'a str>,
+	authority: Option<&'a str>,
+	path: &'a str,
+	query: Option<&'a str>,
+	fragment: Option<&'a str>,
+}
+
+// endregion: --- Types
+
+/// Resolves a URL reference (e.g., an `href` value) against an absolute base URL, as a browser does
+/// (e.g., `../img/a.png` against `https://example.com/docs/page.html` gives `https://example.com/img/a.png`).
+///
+/// - Absolute hierarchical references (e.g., `https://...`) are returned with the dot segments removed, and opaque
+///   ones (e.g., `mailto:...`, `data:...`, `javascript:...`) unchanged.
+/// - Protocol-relative references (e.g., `//cdn.example.com/a.js`) take the scheme of the base.
+/// - Leading and trailing whitespace, tabs, and newlines of the reference are ignored.
+///
+/// Returns `None` when the reference is relative and the base is not an absolute hierarchical URL
+/// (e.g., `/docs/` or `mailto:a@example.com`).
+pub fn resolve_url(base: &str, reference: &str) -> Option<String> {
+	let reference: String = reference
+		.trim_matches(|c: char| c.is_ascii_whitespace() || c.is_ascii_control())
+		.chars()
+		.filter(|c| !matches!(c, '\t' | '\n' | '\r'))
+		.collect();
+	let base = base.trim();
+
+	// Opaque URLs (no authority, non special scheme) have no path to normalize
+	let own_parts = UrlParts::parse(&reference);
+	if let Some(scheme) = own_parts.scheme
+		&& own_parts.authority.is_none()
+		&& !is_special_scheme(scheme)
+	{
+		return Some(reference);
+	}
+
+	let base_parts = Some(UrlParts::parse(base)).filter(|parts| parts.scheme.is_some());
+	// Browsers read `\` as `/` in the http(s) (and other "special" scheme) URLs
+	let reference = match base_parts.as_ref().and_then(|parts| parts.scheme) {
+		Some(scheme) if is_special_scheme(scheme) => backslashes_to_slashes(&reference),
+		_ => reference,
+	};
+
+	let reference_parts = UrlParts::parse(&reference);
+	if let Some(scheme) = reference_parts.scheme {
+		let path = remove_dot_segments(reference_parts.path);
+		return Some(reference_parts.to_url(scheme, reference_parts.authority, &path, reference_parts.query));
+	}
+
+	let base_parts = base_parts?;
+	let scheme = base_parts.scheme?;
+	// Non hierarchical base (e.g., `mailto:`, `data:`), only a fragment could be resolved, which is of no use here
+	if base_parts.authority.is_none() && !base_parts.path.starts_with('/') {
+		return None;
+	}
+
+	let url = if reference_parts.authority.is_some() {
+		let path = remove_dot_segments(reference_parts.path);
+		reference_parts.to_url(scheme, reference_parts.authority, &path, reference_parts.query)
+	} else if reference_parts.path.is_empty() {
+		let query = reference_parts.query.or(base_parts.query);
+		reference_parts.to_url(scheme, base_parts.authority, base_parts.path, query)
+	} else if reference_parts.path.starts_with('/') {
+		let path = remove_dot_segments(reference_parts.path);
+		reference_parts.to_url(scheme, base_parts.authority, &path, reference_parts.query)
+	} else {
+		let path = remove_dot_segments(&merge_paths(&base_parts, reference_parts.path));
+		reference_parts.to_url(scheme, base_parts.authority, &path, reference_parts.query)
+	};
+
+	Some(url)
+}
+
+/// The lowercase scheme of an absolute URL (e.g., `https`), or `None` for a relative reference.
+pub(crate) fn url_scheme(url: &str) -> Option<String> {
+	UrlParts::parse(url.trim()).scheme.map(str::to_ascii_lowercase)
+}
+
+/// The lowercase host of a URL (without user info and port), or `None` when it has no authority.
+pub(crate) fn url_host(url: &str) -> Option<String> {
+	let authority = UrlParts::parse(url.trim()).authority?;
+	let host = authority.rsplit_once('@').map_or(authority, |(_, host)| host);
+	// The port is after the last `:`, unless in an IPv6 address (e.g., `[::1]:8080`)
+	let host = match host.rsplit_once(':') {
+		Some((host, port)) if !port.contains(']') => host,
+		_ => host,
+	};
+	Some(host.to_ascii_lowercase()).filter(|host| !host.is_empty())
+}
+
+// region:    --- UrlParts
+
+impl<'a> UrlParts<'a> {
+	/// Splits a URL or URL reference into its components.
+	fn parse(url: &'a str) -> Self {
+		let (rest, fragment) = match url.split_once('#') {
+			Some((rest, fragment)) => (rest, Some(fragment)),
+			None => (url, None),
+		};
+		let (rest, query) = match rest.split_once('?') {
+			Some((rest, query)) => (rest, Some(query)),
+			None => (rest, None),
+		};
+
+		// A scheme is before the first `:` (e.g., `a/b:c` is a relative path)
+		let (scheme, rest) = match rest.split_once(':') {
+			Some((scheme, rest)) if is_valid_scheme(scheme) => (Some(scheme), rest),
+			_ => (None, rest),
+		};
+
+		let (authority, path) = match rest.strip_prefix("//") {
+			Some(rest) => match rest.find('/') {
+				Some(idx) => (Some(&rest[..idx]), &rest[idx..]),
+				None => (Some(rest), ""),
+			},
+			None => (None, rest),
+		};
+
+		Self {
+			scheme,
+			authority,
+			path,
+			query,
+			fragment,
+		}
+	}
+
+	/// Builds the URL with the given components and the fragment of `self` (the reference).
+	fn to_url(&self, scheme: &str, authority: Option<&str>, path: &str, query: Option<&str>) -> String {
+		let mut url = scheme.to_ascii_lowercase();
+		url.push(':');
+		if let Some(authority) = authority {
+			url.push_str("//");
+			url.push_str(authority);
+			// e.g., `https://example.com` is `https://example.com/`
+			if path.is_empty() {
+				url.push('/');
+			}
+		}
+		url.push_str(path);
+		if let Some(query) = query {
+			url.push('?');
+			url.push_str(query);
+		}
+		if let Some(fragment) = self.fragment {
+			url.push('#');
+			url.push_str(fragment);
+		}
+		url
+	}
+}
+
+// endregion: --- UrlParts
+
+// region:    --- Support
+
+/// `ALPHA *( ALPHA / DIGIT / "+" / "-" / "." )`
+fn is_valid_scheme(scheme: &str) -> bool {
+	let mut chars = scheme.chars();
+	chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+		&& chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+}
+
+fn is_special_scheme(scheme: &str) -> bool {
+	["http", "https", "ws", "wss", "ftp", "file"]
+		.iter()
+		.any(|special| scheme.eq_ignore_ascii_case(special))
+}
+
+/// Replaces the `\` of the reference with `/`, up to the query or fragment.
+fn backslashes_to_slashes(reference: &str) -> String {
+	let end = reference.find(['?', '#']).unwrap_or(reference.len());
+	let mut result = reference[..end].replace('\\', "/");
+	result.push_str(&reference[end..]);
+	result
+}
+
+/// The base path up to its last `/`, followed by the relative reference path (RFC 3986, 5.2.3).
+fn merge_paths(base: &UrlParts, reference_path: &str) -> String {
+	if base.authority.is_some() && base.path.is_empty() {
+		return format!("/{reference_path}");
+	}
+	match base.path.rfind('/') {
+		Some(idx) => format!("{}{reference_path}", &base.path[..=idx]),
+		None => reference_path.to_string(),
+	}
+}
+
+/// Removes the `.` and `..` segments of a path (RFC 3986, 5.2.4). A `..` never goes above the root.
+fn remove_dot_segments(path: &str) -> String {
+	if !path.contains('.') {
+		return path.to_string();
+	}
+
+	let is_absolute = path.starts_with('/');
+	let segments: Vec<&str> = path.split('/').collect();
+	let mut output: Vec<&str> = Vec::with_capacity(segments.len());
+	for (idx, segment) in segments.iter().enumerate() {
+		let is_last = idx == segments.len() - 1;
+		match *segment {
+			"." | ".." => {
+				// The root is the first (empty) segment of an absolute path
+				let min_len = if is_absolute { 1 } else { 0 };
+				if *segment == ".." && output.len() > min_len {
+					output.pop();
+				}
+				// e.g., `/a/b/..` is `/a/`
+				if is_last {
+					output.push("");
+				}
+			}
+			segment => output.push(segment),
+		}
+	}
+
+	output.join("/")
+}
+
+// endregion: --- Support
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	type TestResult<T> = core::result::Result<T, Box<dyn std::error::Error>>;
+
+	use super::*;
+
+	#[test]
+	fn test_url_resolve_url_rfc_examples() -> TestResult<()> {
+		// -- Setup & Fixtures
+		// RFC 3986, 5.4 (with the empty paths as `/`)
+		let fx_base = "http://a/b/c/d;p?q";
+		let fx_cases = [
+			("g", "http://a/b/c/g"),
+			("./g", "http://a/b/c/g"),
+			("g/", "http://a/b/c/g/"),
+			("/g", "http://a/g"),
+			("//g", "http://g/"),
+			("?y", "http://a/b/c/d;p?y"),
+			("g?y#s", "http://a/b/c/g?y#s"),
+			("#s", "http://a/b/c/d;p?q#s"),
+			("", "http://a/b/c/d;p?q"),
+			(".", "http://a/b/c/"),
+			("..", "http://a/b/"),
+			("../g", "http://a/b/g"),
+			("../../g", "http://a/g"),
+			("../../../g", "http://a/g"),
+			("/./g", "http://a/g"),
+			("g.", "http://a/b/c/g."),
+			("..g", "http://a/b/c/..g"),
+			("./g/.", "http://a/b/c/g/"),
+			("g/../h", "http://a/b/c/h"),
+			("g;x=1/../y", "http://a/b/c/y"),
+			("g:h", "g:h"),
+		];
+
+		// -- Exec & Check
+		for (reference, expected) in fx_cases {
+			assert_eq!(
+				resolve_url(fx_base, reference).as_deref(),
+				Some(expected),
+				"reference: {reference}"
+			);
+		}
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_url_resolve_url_edge_cases() -> TestResult<()> {
+		// -- Setup & Fixtures
+		let fx_base = "https://example.com/docs/guide/intro.html";
+
+		// -- Exec & Check
+		// Protocol-relative
+		assert_eq!(
+			resolve_url(fx_base, "//cdn.example.com/lib.js").as_deref(),
+			Some("https://cdn.example.com/lib.js")
+		);
+		// Above the root
+		assert_eq!(
+			resolve_url(fx_base, "../../../../img/a.png").as_deref(),
+			Some("https://example.com/img/a.png")
+		);
+		// Whitespace, newlines, and backslashes
+		assert_eq!(
+			resolve_url(fx_base, "  ..\\api/\nref.html ").as_deref(),
+			Some("https://example.com/docs/api/ref.html")
+		);
+		// Base without path
+		assert_eq!(
+			resolve_url("https://example.com", "a/b").as_deref(),
+			Some("https://example.com/a/b")
+		);
+		// Absolute references, whatever the base
+		assert_eq!(
+			resolve_url("/relative/base", "HTTPS://Example.com/a/../b").as_deref(),
+			Some("https://Example.com/b")
+		);
+		assert_eq!(
+			resolve_url(fx_base, "mailto:me@example.com").as_deref(),
+			Some("mailto:me@example.com")
+		);
+		assert_eq!(
+			resolve_url(fx_base, "javascript:void(0)").as_deref(),
+			Some("javascript:void(0)")
+		);
+		// Relative references without an absolute hierarchical base
+		assert_eq!(resolve_url("/relative/base", "a.html"), None);
+		assert_eq!(resolve_url("mailto:me@example.com", "a.html"), None);
+		// Scheme and host
+		assert_eq!(url_scheme("HTTPS://a.com").as_deref(), Some("https"));
+		assert_eq!(url_scheme("../a.html"), None);
+		assert_eq!(
+			url_host("https://user@Example.com:8080/a").as_deref(),
+			Some("example.com")
+		);
+		assert_eq!(url_host("http://[::1]:80/").as_deref(), Some("[::1]"));
+		assert_eq!(url_host("mailto:me@example.com"), None);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_url_resolve_url_opaque() -> TestResult<()> {
+		// -- Setup & Fixtures
+		let fx_base = "http://a/b/c";
+
+		// -- Exec & Check
+		assert_eq!(
+			resolve_url(fx_base, "data:text/plain,a/../b").as_deref(),
+			Some("data:text/plain,a/../b")
+		);
+		assert_eq!(
+			resolve_url(fx_base, "mailto:x/../y@z").as_deref(),
+			Some("mailto:x/../y@z")
+		);
+		assert_eq!(
+			resolve_url(fx_base, " javascript:go('./a/../b')\n").as_deref(),
+			Some("javascript:go('./a/../b')")
+		);
+		// No backslash rewrite in opaque URLs
+		assert_eq!(resolve_url(fx_base, "data:,a\\b").as_deref(), Some("data:,a\\b"));
+		// Hierarchical references are still normalized
+		assert_eq!(resolve_url(fx_base, "ftp:/x/../y").as_deref(), Some("ftp:/y"));
+		assert_eq!(
+			resolve_url(fx_base, "urn://host/a/../b").as_deref(),
+			Some("urn://host/b")
+		);
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
@@ -0,0 +1,119 @@
+use super::resolve_url;
+use scraper::{ElementRef, Html};
+
+// region:    --- Constants
+
+/// The attributes holding a URL, rewritten with a base URL.
+const URL_ATTRS: &[&str] = &["href", "src", "action"];
+
+// endregion: --- Constants
+
+/// The base URL of the document: the first `<base href>` resolved against `base_url`, or `base_url` itself.
+pub(crate) fn effective_base_url(html: &Html, base_url: &str) -> String {
+	html.root_element()
+		.descendants()
+		.filter_map(ElementRef::wrap)
+		.find(|el| el.value().name() == "base" && el.value().attr("href").is_some())
+		.and_then(|base| base.value().attr("href"))
+		.and_then(|href| resolve_url(base_url, href))
+		.unwrap_or_else(|| base_url.to_string())
+}
+
+/// The attribute value with its URL(s) resolved against the base URL (`href`, `src`, `action`, and `srcset`).
+///
+/// Returns `None` when the attribute is not a URL attribute or the URL cannot be resolved (kept as is).
+pub(crate) fn resolve_url_attr(name: &str, value: &str, base_url: &str) -> Option<String> {
+	if name == "srcset" {
+		Some(resolve_srcset(value, base_url))
+	} else if URL_ATTRS.contains(&name) {
+		resolve_url(base_url, value)
+	} else {
+		None
+	}
+}
+
+// region:    --- Support
+
+/// Resolves each image candidate URL of a `srcset` (e.g., `a.png 1x, b.png 2x`), keeping the descriptors.
+fn resolve_srcset(srcset: &str, base_url: &str) -> String {
+	let mut candidates: Vec<String> = Vec::new();
+	let mut rest = srcset;
+
+	loop {
+		rest = rest.trim_start_matches(|c: char| c.is_ascii_whitespace() || c == ',');
+		if rest.is_empty() {
+			break;
+		}
+
+		let url_end = rest.find(|c: char| c.is_ascii_whitespace()).unwrap_or(rest.len());
+		let (mut url, mut descriptor) = (&rest[..url_end], "");
+		rest = &rest[url_end..];
+
+		// A URL ending with a comma has no descriptor (e.g., `a.png,b.png 2x`)
+		if url.ends_with(',') {
+			url = url.trim_end_matches(',');
+		} else {
+			// The descriptor goes to the next comma outside parentheses
+			let mut depth = 0usize;
+			let descriptor_end = rest
+				.char_indices()
+				.find(|(_, c)| match c {
+					'(' => {
+						depth += 1;
+						false
+					}
+					')' => {
+						depth = depth.saturating_sub(1);
+						false
+					}
+					',' => depth == 0,
+					_ => false,
+				})
+				.map(|(idx, _)| idx)
+				.unwrap_or(rest.len());
+			descriptor = rest[..descriptor_end].trim();
+			rest = &rest[descriptor_end..];
+		}
+
+		let url = resolve_url(base_url, url).unwrap_or_else(|| url.to_string());
+		if descriptor.is_empty() {
+			candidates.push(url);
+		} else {
+			candidates.push(format!("{url} {descriptor}"));
+		}
+	}
+
+	candidates.join(", ")
+}
+
+// endregion: --- Support
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	type TestResult<T> = core::result::Result<T, Box<dyn std::error::Error>>;
+
+	use super::*;
+
+	#[test]
+	fn test_url_resolve_url_attr_srcset() -> TestResult<()> {
+		// -- Setup & Fixtures
+		let fx_base = "https://example.com/blog/post.html";
+
+		// -- Exec
+		let srcset = resolve_url_attr("srcset", " img/a.png 1x,\n  /b.png 2x,c.png,", fx_base);
+		let title = resolve_url_attr("title", "img/a.png", fx_base);
+
+		// -- Check
+		assert_eq!(
+			srcset.as_deref(),
+			Some("https://example.com/blog/img/a.png 1x, https://example.com/b.png 2x, https://example.com/blog/c.png")
+		);
+		assert_eq!(title, None);
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests